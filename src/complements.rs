@@ -0,0 +1,35 @@
+/// Returns complement of each group.
+///
+/// For each group index, the result contains the concatenation of all other
+/// groups (in group order). This is useful for leave-one-group-out style
+/// cross-validation.
+///
+/// # Examples
+///
+/// ```
+/// # use random_grouping::complements;
+/// let samples = [1, 2, 3, 4, 5];
+/// let groups = vec![vec![&samples[0]], vec![&samples[1], &samples[2]], vec![&samples[3]]];
+///
+/// let result = complements(&groups);
+///
+/// assert_eq!(result[0], vec![&2, &3, &4]);
+/// assert_eq!(result[1], vec![&1, &4]);
+/// assert_eq!(result[2], vec![&1, &2, &3]);
+/// ```
+#[must_use]
+pub fn complements<'t, T>(groups: &[Vec<&'t T>]) -> Vec<Vec<&'t T>> {
+    let total = groups.iter().map(Vec::len).sum::<usize>();
+    let mut results = Vec::with_capacity(groups.len());
+
+    for (idx, group) in groups.iter().enumerate() {
+        let mut result = Vec::with_capacity(total - group.len());
+        for other in groups.iter().take(idx).chain(groups.iter().skip(idx + 1)) {
+            result.extend_from_slice(other);
+        }
+
+        results.push(result);
+    }
+
+    results
+}
@@ -0,0 +1,62 @@
+/// Report of grouping by ratios.
+///
+/// This struct is returned by
+/// [`divide_by_ratio_reported`](crate::RandomGrouping::divide_by_ratio_reported)
+/// method together with groups. It is useful for auditing how requested
+/// ratios are turned into actual group sizes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DivideReport {
+    /// Requested ratios.
+    ratios: Vec<f64>,
+    /// Effective group sizes.
+    sizes: Vec<usize>,
+    /// Count of samples not placed in any group.
+    dropped: usize,
+    /// Realized ratios of each group.
+    realized_ratios: Vec<f64>,
+}
+
+impl DivideReport {
+    /// Create an instance from requested ratios, effective sizes and samples length.
+    pub(crate) fn new(ratios: &[f64], sizes: &[usize], len: usize) -> Self {
+        let placed = sizes.iter().sum::<usize>();
+        let realized_ratio = |&size: &usize| match len {
+            0 => 0.0,
+            _ => size as f64 / len as f64,
+        };
+
+        Self {
+            ratios: ratios.to_vec(),
+            sizes: sizes.to_vec(),
+            dropped: len - placed,
+            realized_ratios: sizes.iter().map(realized_ratio).collect(),
+        }
+    }
+
+    /// Returns requested ratios.
+    #[must_use]
+    pub fn ratios(&self) -> &[f64] {
+        &self.ratios
+    }
+
+    /// Returns effective group sizes.
+    #[must_use]
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    /// Returns count of samples not placed in any group.
+    #[must_use]
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns realized ratios of each group.
+    ///
+    /// Each value is group size divided by samples length (or `0.0` if
+    /// samples are empty).
+    #[must_use]
+    pub fn realized_ratios(&self) -> &[f64] {
+        &self.realized_ratios
+    }
+}
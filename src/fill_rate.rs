@@ -0,0 +1,41 @@
+use std::cmp::Ordering;
+
+/// Fill rate of a group.
+///
+/// Ordered so that less filled groups are greater, which makes
+/// [`BinaryHeap`](std::collections::BinaryHeap) pop the least full group
+/// first. Rates are compared by cross multiplication (without floating point
+/// errors), and ties are broken by lower index.
+#[derive(Debug, Clone, Copy)]
+pub struct FillRate {
+    /// Group index.
+    pub index: usize,
+    /// Current group length.
+    pub len: usize,
+    /// Target group size.
+    pub size: usize,
+}
+
+impl PartialEq for FillRate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FillRate {}
+
+impl PartialOrd for FillRate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FillRate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let fill_self = self.len * other.size;
+        let fill_other = other.len * self.size;
+        fill_other
+            .cmp(&fill_self)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
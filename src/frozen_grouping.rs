@@ -0,0 +1,98 @@
+use crate::sampling_strategy::SamplingStrategy;
+use crate::RandomGrouping;
+use rand::SeedableRng;
+use rand_pcg::Pcg32;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Frozen random grouping executor.
+///
+/// This struct is created by [`freeze`](RandomGrouping::freeze) method.
+/// Unlike [`RandomGrouping`], grouping methods of this struct take `&self`.
+/// Instead of advancing a shared random number generator, each call creates
+/// a fresh one from the frozen seed and an internal call counter (mixed by
+/// SplitMix64, so that each call is independent of others). Therefore,
+/// split results are deterministic and differ from call to call.
+pub struct FrozenGrouping {
+    /// Flag to adjust the order inside groups.
+    stable: bool,
+    /// Flag to adjust the order across groups.
+    global_stable: bool,
+    /// Strategy to select samples.
+    sampling: Arc<dyn SamplingStrategy>,
+    /// Flag to reject empty group specification for non-empty samples.
+    require_groups: bool,
+    /// Frozen random number seed.
+    seed: u64,
+    /// Number of calls so far.
+    counter: AtomicU64,
+}
+
+impl FrozenGrouping {
+    /// Create an instance with settings and seed.
+    pub(crate) fn new(
+        stable: bool,
+        global_stable: bool,
+        sampling: Arc<dyn SamplingStrategy>,
+        require_groups: bool,
+        seed: u64,
+    ) -> Self {
+        Self {
+            stable,
+            global_stable,
+            sampling,
+            require_groups,
+            seed,
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns `true` if original order is keeped at grouping.
+    #[must_use]
+    pub fn stable(&self) -> bool {
+        self.stable
+    }
+
+    /// Returns `true` if original order is keeped across all groups.
+    #[must_use]
+    pub fn global_stable(&self) -> bool {
+        self.global_stable
+    }
+
+    /// Returns frozen random number seed.
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Group a slice of samples, with specifying the sizes of each group.
+    ///
+    /// See also [`divide_slice_by_size`](RandomGrouping::divide_slice_by_size).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the samples length is less than group size total.
+    pub fn split_by_size<'t, T>(&self, samples: &'t [T], sizes: &[usize]) -> Vec<Vec<&'t T>> {
+        let count = self.counter.fetch_add(1, Ordering::Relaxed);
+        let mut rng = Pcg32::seed_from_u64(splitmix64(self.seed, count));
+        let mut rg = RandomGrouping::from_rng(&mut rng)
+            .with_stable(self.stable)
+            .with_global_stable(self.global_stable)
+            .with_require_groups(self.require_groups)
+            .with_shared_sampling_strategy(self.sampling.clone());
+        rg.divide_slice_by_size(samples, sizes)
+    }
+}
+
+/// Returns the `index`-th output of SplitMix64 seeded with `seed`.
+///
+/// Unlike PCG streams selected by increment, outputs for successive
+/// indices are not correlated with each other.
+fn splitmix64(seed: u64, index: u64) -> u64 {
+    const GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+    let mut z = seed.wrapping_add(GAMMA.wrapping_mul(index.wrapping_add(1)));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,30 @@
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+
+/// Distribution over group indices.
+///
+/// This struct is created by
+/// [`group_index_distribution`](crate::RandomGrouping::group_index_distribution)
+/// method. It samples a group index with probability proportional to its
+/// group size, so that group assignments can be drawn one at a time.
+#[derive(Debug, Clone)]
+pub struct GroupIndexDist {
+    /// Weighted index distribution by group sizes.
+    inner: WeightedIndex<usize>,
+}
+
+impl GroupIndexDist {
+    /// Create an instance from group sizes.
+    pub(crate) fn new(sizes: &[usize]) -> Self {
+        match WeightedIndex::new(sizes) {
+            Ok(inner) => Self { inner },
+            Err(_) => panic!("Sizes total is zero."),
+        }
+    }
+}
+
+impl Distribution<usize> for GroupIndexDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        self.inner.sample(rng)
+    }
+}
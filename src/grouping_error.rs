@@ -0,0 +1,45 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+
+/// Error of grouping specification.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GroupingError {
+    /// Ratios contains NaN.
+    NanRatio,
+
+    /// Ratios contains infinite value.
+    InfiniteRatio,
+
+    /// Ratios contains negative value.
+    NegativeRatio,
+
+    /// Ratios total is greater than 1.
+    RatioTotalOver,
+
+    /// No groups are specified for non-empty samples.
+    NoGroupsSpecified,
+
+    /// Weights total is zero.
+    ZeroWeightSum,
+
+    /// Weights total is infinite.
+    InfiniteWeightSum,
+}
+
+impl Display for GroupingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let msg = match self {
+            Self::NanRatio => "Ratios contains NaN.",
+            Self::InfiniteRatio => "Ratios contains infinite value.",
+            Self::NegativeRatio => "Ratios contains negative value.",
+            Self::RatioTotalOver => "Ratios total is greater than 1.",
+            Self::NoGroupsSpecified => "No groups are specified for non-empty samples.",
+            Self::ZeroWeightSum => "Weights total is zero.",
+            Self::InfiniteWeightSum => "Weights total is infinite.",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+impl Error for GroupingError {}
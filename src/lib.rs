@@ -2,10 +2,31 @@
  * Utility for random grouping.
  */
 
+mod complements;
+mod divide_report;
+mod fill_rate;
+mod frozen_grouping;
+mod group_index_dist;
+mod grouping_error;
 mod random_grouping;
+mod ratios;
+mod sampling_strategy;
 mod size_rounding;
 mod sized_iter;
 mod staff;
+mod stats_summary;
+mod tie_break;
+mod timing;
 
+pub use crate::complements::*;
+pub use crate::divide_report::*;
+pub use crate::frozen_grouping::*;
+pub use crate::group_index_dist::*;
+pub use crate::grouping_error::*;
 pub use crate::random_grouping::*;
+pub use crate::ratios::*;
+pub use crate::sampling_strategy::*;
+pub use crate::stats_summary::*;
+pub use crate::timing::*;
 pub use size_rounding::*;
+pub use tie_break::*;
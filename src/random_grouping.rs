@@ -1,14 +1,49 @@
+use crate::divide_report::DivideReport;
+use crate::fill_rate::FillRate;
+use crate::frozen_grouping::FrozenGrouping;
+use crate::group_index_dist::GroupIndexDist;
+use crate::grouping_error::GroupingError;
+use crate::ratios::{self, normalize_ratios, Ratios};
+use crate::sampling_strategy::{SamplingStrategy, UniformSampling};
 use crate::size_rounding::SizeRounding;
 use crate::sized_iter::SizedIter;
 use crate::staff::Staff;
+use crate::stats_summary::StatsSummary;
+use crate::tie_break::TieBreak;
+use crate::timing::Timing;
 use rand::rngs::ThreadRng;
-use rand::seq::index::sample;
 use rand::seq::SliceRandom;
+use rand::Rng;
 use rand::RngCore;
 use rand::SeedableRng;
+#[cfg(feature = "rand_distr")]
+use rand_distr::Distribution;
 use rand_pcg::Pcg32;
 use simple_scan::IteratorSimpleScanExt;
-use std::collections::BTreeMap;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Random number seed used by [`RandomGrouping::new`].
+pub const DEFAULT_SEED: u64 = 0;
+
+/// Group label of samples not placed in any group.
+///
+/// See also [`RandomGrouping::group_labels_by_size`].
+pub const UNPLACED: usize = usize::MAX;
+
+/// Basis points equivalent to the whole.
+const BPS_WHOLE: u64 = 10000;
+
+/// Denominator of ratios in integer ratio math.
+///
+/// See also [`RandomGrouping::integer_ratio_math`].
+const RATIO_WHOLE: u64 = 1_000_000_000;
+
+/// Number of placed samples between cancellation checks.
+const CANCEL_CHECK_INTERVAL: usize = 1024;
 
 /// Random grouping executor.
 ///
@@ -34,14 +69,33 @@ use std::collections::BTreeMap;
 pub struct RandomGrouping<'r> {
     /// Flag to adjust the order inside groups.
     stable: bool,
+    /// Flag to adjust the order across groups.
+    global_stable: bool,
     /// Rounding strategy for group size.
     rounding: SizeRounding,
+    /// Tie-breaking strategy for remainder distribution.
+    tiebreak: TieBreak,
+    /// Strategy to select samples.
+    sampling: Arc<dyn SamplingStrategy>,
+    /// Flag to reject empty group specification for non-empty samples.
+    require_groups: bool,
+    /// Flag to treat equal samples as a same sample.
+    treat_equal_as_same: bool,
+    /// Flag to convert ratios to sizes with integer arithmetic.
+    integer_ratio_math: bool,
+    /// Flag to record timing of grouping.
+    timing: bool,
+    /// Timing of the last grouping.
+    last_timing: Option<Timing>,
     /// Random number generator.
     rng: Staff<'r, dyn RngCore>,
 }
 
 impl<'r> RandomGrouping<'r> {
     /// Creates an instance with default random number seed.
+    ///
+    /// The seed is [`DEFAULT_SEED`], so this is same as
+    /// [`from_seed(DEFAULT_SEED)`](Self::from_seed).
     #[must_use]
     pub fn new() -> Self {
         Self::default()
@@ -65,6 +119,29 @@ impl<'r> RandomGrouping<'r> {
         }
     }
 
+    /// Creates an instance with the random number seed made from a byte string.
+    ///
+    /// The bytes are hashed by 64-bit FNV-1a, and the hash is used as seed
+    /// (See [`from_seed`](Self::from_seed)). This hash algorithm is fixed,
+    /// so the same key always gives the same result on every platform.
+    #[must_use]
+    pub fn from_seed_bytes(key: &[u8]) -> Self {
+        return Self::from_seed(fnv1a_64(key));
+
+        fn fnv1a_64(bytes: &[u8]) -> u64 {
+            const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+            const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+            let mut result = OFFSET_BASIS;
+            for &byte in bytes {
+                result ^= byte as u64;
+                result = result.wrapping_mul(PRIME);
+            }
+
+            result
+        }
+    }
+
     /// Creates an instance with the specified random number generator.
     #[must_use]
     pub fn from_rng(rng: &'r mut dyn RngCore) -> Self {
@@ -82,6 +159,28 @@ impl<'r> RandomGrouping<'r> {
         self.stable
     }
 
+    /// Returns `true` if original order is keeped across all groups.
+    ///
+    /// If this is `true`, selected samples are sorted in original order
+    /// before they are split into groups. Therefore, concatenation of all
+    /// groups keeps original order, and each group is a consecutive run of
+    /// the selected samples. Note that only selection of samples is random
+    /// in this case, and group of each selected sample is decided by its
+    /// position. If all samples are selected, the result is not random.
+    ///
+    /// Compared to [`stable`](Self::stable), which only keeps the order
+    /// inside each group, this keeps the order across groups too. So, if
+    /// this is `true`, [`stable`](Self::stable) is ignored.
+    ///
+    /// This is ignored by [`divide_by_size_least_full`](Self::divide_by_size_least_full)
+    /// and [`divide_by_size_weighted_strata`](Self::divide_by_size_weighted_strata).
+    ///
+    /// Default value is `false`.
+    #[must_use]
+    pub fn global_stable(&self) -> bool {
+        self.global_stable
+    }
+
     /// Returns rounding strategy for group size.
     ///
     /// Default value is [`Floor`](SizeRounding::Floor).
@@ -90,6 +189,107 @@ impl<'r> RandomGrouping<'r> {
         self.rounding
     }
 
+    /// Returns tie-breaking strategy for remainder distribution.
+    ///
+    /// This is used only with [`LargestRemainder`](SizeRounding::LargestRemainder)
+    /// rounding strategy.
+    ///
+    /// Default value is [`ByIndex`](TieBreak::ByIndex).
+    #[must_use]
+    pub fn remainder_tiebreak(&self) -> TieBreak {
+        self.tiebreak
+    }
+
+    /// Returns strategy to select samples.
+    ///
+    /// This is not used by following cases.
+    ///
+    /// * [`divide_by_size_weighted_strata`](Self::divide_by_size_weighted_strata)
+    ///   (samples are drawn by their weights).
+    /// * [`divide_by_size`](Self::divide_by_size) and methods based on it,
+    ///   with a single group holding all samples.
+    ///
+    /// Default value is [`UniformSampling`].
+    #[must_use]
+    pub fn sampling_strategy(&self) -> &dyn SamplingStrategy {
+        &*self.sampling
+    }
+
+    /// Returns `true` if empty group specification is rejected for non-empty samples.
+    ///
+    /// If this is `true`, grouping methods panic with
+    /// [`NoGroupsSpecified`](GroupingError::NoGroupsSpecified) message when
+    /// group sizes or ratios are empty but samples are not. Otherwise, such
+    /// grouping returns no groups.
+    ///
+    /// Default value is `false`.
+    #[must_use]
+    pub fn require_groups(&self) -> bool {
+        self.require_groups
+    }
+
+    /// Returns `true` if equal samples are treated as a same sample.
+    ///
+    /// This is supported only by [`divide_slice_by_size_eq`](Self::divide_slice_by_size_eq).
+    /// All other grouping methods always distinguish samples by position, so
+    /// they panic if this is `true`, instead of ignoring it.
+    ///
+    /// If this is `true`, samples with equal values are always placed into
+    /// the same group. Note that group sizes are then counted in distinct
+    /// values, not in samples. For example, a group of size 2 can hold
+    /// samples `[a, a, b]`. Otherwise, samples are distinguished by position
+    /// and group sizes are counted in samples.
+    ///
+    /// Default value is `false`.
+    #[must_use]
+    pub fn treat_equal_as_same(&self) -> bool {
+        self.treat_equal_as_same
+    }
+
+    /// Returns `true` if ratios are converted to sizes with integer arithmetic.
+    ///
+    /// If this is `true`, each ratio is converted to a fraction with
+    /// denominator 10^9 (numerator is rounded), and group sizes are
+    /// calculated from it only with integer arithmetic. Therefore, group
+    /// sizes are same on every platform. Otherwise, group sizes are
+    /// calculated with `f64` arithmetic.
+    ///
+    /// Both give same sizes in most cases, but they can differ when the
+    /// exact product of ratio and samples length is near a rounding
+    /// boundary, or when ratio has precision finer than 10^-9. For example,
+    /// `0.29` of 100 samples with [`Floor`](SizeRounding::Floor) rounding is
+    /// 29 with integers but 28 with `f64` (because it is 28.999...), and
+    /// `1e-10` of 10^10 samples is 0 with integers but 1 with `f64`.
+    ///
+    /// Default value is `false`.
+    #[must_use]
+    pub fn integer_ratio_math(&self) -> bool {
+        self.integer_ratio_math
+    }
+
+    /// Returns `true` if timing of grouping is recorded.
+    ///
+    /// If this is `true`, [`divide_by_size`](Self::divide_by_size) (and
+    /// methods based on it) records timing of each phase, and it can be
+    /// retrieved by [`last_timing`](Self::last_timing). Otherwise, the
+    /// clock is never read.
+    ///
+    /// Default value is `false`.
+    #[must_use]
+    pub fn timing(&self) -> bool {
+        self.timing
+    }
+
+    /// Returns timing of the last grouping.
+    ///
+    /// Returns `None` if [`timing`](Self::timing) is `false`, no grouping
+    /// is done yet, or the last grouping does not record timing (grouping
+    /// methods not based on [`divide_by_size`](Self::divide_by_size)).
+    #[must_use]
+    pub fn last_timing(&self) -> Option<Timing> {
+        self.last_timing
+    }
+
     /// Set stable flag.
     ///
     /// See also [`stable`](Self::stable).
@@ -98,6 +298,14 @@ impl<'r> RandomGrouping<'r> {
         self
     }
 
+    /// Set global stable flag.
+    ///
+    /// See also [`global_stable`](Self::global_stable).
+    pub fn with_global_stable(mut self, value: bool) -> Self {
+        self.global_stable = value;
+        self
+    }
+
     /// Set rounding strategy for group size.
     ///
     /// See also [`rounding`](Self::rounding).
@@ -106,12 +314,119 @@ impl<'r> RandomGrouping<'r> {
         self
     }
 
+    /// Set tie-breaking strategy for remainder distribution.
+    ///
+    /// See also [`remainder_tiebreak`](Self::remainder_tiebreak).
+    pub fn with_remainder_tiebreak(mut self, value: TieBreak) -> Self {
+        self.tiebreak = value;
+        self
+    }
+
+    /// Set strategy to select samples.
+    ///
+    /// See also [`sampling_strategy`](Self::sampling_strategy).
+    pub fn with_sampling_strategy(self, value: Box<dyn SamplingStrategy>) -> Self {
+        self.with_shared_sampling_strategy(Arc::from(value))
+    }
+
+    /// Set strategy to select samples, with sharing it.
+    pub(crate) fn with_shared_sampling_strategy(
+        mut self,
+        value: Arc<dyn SamplingStrategy>,
+    ) -> Self {
+        self.sampling = value;
+        self
+    }
+
+    /// Set flag to reject empty group specification for non-empty samples.
+    ///
+    /// See also [`require_groups`](Self::require_groups).
+    pub fn with_require_groups(mut self, value: bool) -> Self {
+        self.require_groups = value;
+        self
+    }
+
+    /// Set flag to treat equal samples as a same sample.
+    ///
+    /// See also [`treat_equal_as_same`](Self::treat_equal_as_same).
+    pub fn with_treat_equal_as_same(mut self, value: bool) -> Self {
+        self.treat_equal_as_same = value;
+        self
+    }
+
+    /// Set flag to convert ratios to sizes with integer arithmetic.
+    ///
+    /// See also [`integer_ratio_math`](Self::integer_ratio_math).
+    pub fn with_integer_ratio_math(mut self, value: bool) -> Self {
+        self.integer_ratio_math = value;
+        self
+    }
+
+    /// Set flag to record timing of grouping.
+    ///
+    /// Recorded timing is cleared. See also [`timing`](Self::timing).
+    pub fn with_timing(mut self, value: bool) -> Self {
+        self.timing = value;
+        self.last_timing = None;
+        self
+    }
+
+    /// Creates an instance with current settings and the specified random number seed.
+    ///
+    /// Settings are copied, but the random number generator is not shared.
+    /// It works even if this instance is created by [`from_rng`](Self::from_rng).
+    #[must_use]
+    pub fn clone_config(&self, seed: u64) -> Self {
+        Self {
+            stable: self.stable,
+            global_stable: self.global_stable,
+            rounding: self.rounding,
+            tiebreak: self.tiebreak,
+            sampling: self.sampling.clone(),
+            require_groups: self.require_groups,
+            treat_equal_as_same: self.treat_equal_as_same,
+            integer_ratio_math: self.integer_ratio_math,
+            timing: self.timing,
+            last_timing: None,
+            rng: Staff::new_own(Box::new(Pcg32::seed_from_u64(seed))),
+        }
+    }
+
+    /// Creates a frozen instance with current settings and the specified seed.
+    ///
+    /// See also [`FrozenGrouping`].
+    #[must_use]
+    pub fn freeze(&self, seed: u64) -> FrozenGrouping {
+        let sampling = self.sampling.clone();
+        let (stable, global_stable) = (self.stable, self.global_stable);
+        FrozenGrouping::new(stable, global_stable, sampling, self.require_groups, seed)
+    }
+
+    /// Returns a distribution over group indices weighted by group sizes.
+    ///
+    /// See also [`GroupIndexDist`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if group size total is zero.
+    #[must_use]
+    pub fn group_index_distribution(&self, sizes: &[usize]) -> GroupIndexDist {
+        GroupIndexDist::new(sizes)
+    }
+
     /// Group a collection of samples, with specifying the sizes of each group.
     ///
     /// Behavior of this method is affected by following values.
     ///
     /// * Random number generator and its seed (at construction).
     /// * Orders inside each groups (See [`stable`](Self::stable)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
+    ///
+    /// If `sizes` is a single group holding all samples, sample selection is
+    /// skipped (and so is [`sampling_strategy`](Self::sampling_strategy)),
+    /// and samples are only shuffled if needed. Group contents are same as
+    /// other cases, but random numbers consumed are not. Therefore, following
+    /// groupings with the same random number generator give other results.
     ///
     /// # Panics
     ///
@@ -120,17 +435,38 @@ impl<'r> RandomGrouping<'r> {
     where
         I: IntoIterator,
     {
+        self.check_by_position();
         let mut samples_iter = samples.into_iter();
         let mut samples_iter = SizedIter::new(&mut samples_iter);
         let samples_len = samples_iter.size_hint().1.unwrap();
         let select_len = sizes.iter().sum::<usize>();
 
+        self.check_groups_spec(sizes.len(), samples_len);
+
         if samples_len < sizes.iter().sum() {
             panic!("Samples length is greater than sizes total.");
         }
 
+        let started = self.timing.then(Instant::now);
+
+        if sizes == [samples_len] {
+            let sampled = self.timing.then(Instant::now);
+            let mut group = samples_iter.collect::<Vec<_>>();
+            let gathered = self.timing.then(Instant::now);
+            if !self.stable && !self.global_stable {
+                group.shuffle(&mut *self.rng);
+            }
+
+            self.record_timing(started, sampled, gathered);
+            return vec![group];
+        }
+
         let mut table = BTreeMap::new();
-        let idxs = sample(&mut *self.rng, samples_len, select_len).into_vec();
+        let mut idxs = self.select(samples_len, select_len);
+        if self.global_stable {
+            idxs.sort_unstable();
+        }
+
         let group_areas = sizes.iter().cloned().trace2(0, |total, size| total + size);
         let group_ranges = group_areas.map(|(lower, upper)| lower..upper);
 
@@ -140,26 +476,29 @@ impl<'r> RandomGrouping<'r> {
             }
         }
 
+        let sampled = self.timing.then(Instant::now);
         let mut results = Vec::with_capacity(sizes.len());
-        let mut prev_idx = -1;
+        let mut prev_idx = None;
 
         for &size in sizes {
             results.push(Vec::with_capacity(size));
         }
 
         for (idx, group_idx) in table {
-            let idx_progress = (idx as isize - prev_idx) as usize;
-            let sample = samples_iter.nth(idx_progress - 1).unwrap();
+            let skip_len = prev_idx.map_or(idx, |prev_idx: usize| idx - prev_idx - 1);
+            let sample = samples_iter.nth(skip_len).unwrap();
             results[group_idx].push(sample);
-            prev_idx = idx as isize;
+            prev_idx = Some(idx);
         }
 
-        if !self.stable {
+        let gathered = self.timing.then(Instant::now);
+        if !self.stable && !self.global_stable {
             for group in results.iter_mut() {
                 group.shuffle(&mut *self.rng);
             }
         }
 
+        self.record_timing(started, sampled, gathered);
         results
     }
 
@@ -169,7 +508,9 @@ impl<'r> RandomGrouping<'r> {
     ///
     /// * Random number generator and its seed (at construction).
     /// * Orders inside each groups (See [`stable`](Self::stable)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
     /// * Rounding strategy for group size (See [`rounding`](Self::rounding)).
+    /// * Integer ratio math flag (See [`integer_ratio_math`](Self::integer_ratio_math)).
     ///
     /// # Panics
     ///
@@ -183,13 +524,7 @@ impl<'r> RandomGrouping<'r> {
     where
         I: IntoIterator,
     {
-        if !ratios.iter().all(Self::check_ratio) {
-            panic!("Ratios contains illegal value.");
-        }
-
-        if ratios.iter().sum::<f64>() > 1.0 {
-            panic!("Ratios total is greater than 1.");
-        }
+        ratios::validate(ratios).unwrap_or_else(|e| panic!("{}", e));
 
         let mut samples_iter = samples.into_iter();
         let samples_iter = SizedIter::new(&mut samples_iter);
@@ -198,16 +533,100 @@ impl<'r> RandomGrouping<'r> {
         self.divide_by_size(samples_iter, &sizes)
     }
 
+    /// Group a collection of samples, with specifying the validated ratios of each group.
+    ///
+    /// Compared to [`divide_by_ratio`](Self::divide_by_ratio), this method
+    /// skips checking ratios, because [`Ratios`] is already checked at its
+    /// construction.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Random number generator and its seed (at construction).
+    /// * Orders inside each groups (See [`stable`](Self::stable)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
+    /// * Rounding strategy for group size (See [`rounding`](Self::rounding)).
+    /// * Integer ratio math flag (See [`integer_ratio_math`](Self::integer_ratio_math)).
+    pub fn divide_by_valid_ratio<I>(&mut self, samples: I, ratios: &Ratios) -> Vec<Vec<I::Item>>
+    where
+        I: IntoIterator,
+    {
+        let mut samples_iter = samples.into_iter();
+        let samples_iter = SizedIter::new(&mut samples_iter);
+        let samples_len = samples_iter.size_hint().1.unwrap();
+        let sizes = self.ratios_to_sizes(ratios.as_slice(), samples_len);
+        self.divide_by_size(samples_iter, &sizes)
+    }
+
+    /// Group a collection of samples, with specifying the relative weights of
+    /// each group.
+    ///
+    /// Weights are normalized to ratios by [`normalize_ratios`], so that all
+    /// weights total is the whole. For example, `[1.0, 3.0]` is same as ratios
+    /// `[0.25, 0.75]`.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Random number generator and its seed (at construction).
+    /// * Orders inside each groups (See [`stable`](Self::stable)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
+    /// * Rounding strategy for group size (See [`rounding`](Self::rounding)).
+    /// * Integer ratio math flag (See [`integer_ratio_math`](Self::integer_ratio_math)).
+    ///
+    /// # Panics
+    ///
+    /// Panics with [`GroupingError`] message if [`normalize_ratios`] fails.
+    pub fn divide_by_relative<I>(&mut self, samples: I, weights: &[f64]) -> Vec<Vec<I::Item>>
+    where
+        I: IntoIterator,
+    {
+        let ratios = normalize_ratios(weights).unwrap_or_else(|e| panic!("{}", e));
+        self.divide_by_valid_ratio(samples, &ratios)
+    }
+
+    /// Group a collection of samples, with specifying the ratios of each group,
+    /// and report how the ratios are realized.
+    ///
+    /// Groups are same as [`divide_by_ratio`](Self::divide_by_ratio).
+    /// In addition, [`DivideReport`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`divide_by_ratio`](Self::divide_by_ratio).
+    pub fn divide_by_ratio_reported<I>(
+        &mut self,
+        samples: I,
+        ratios: &[f64],
+    ) -> (Vec<Vec<I::Item>>, DivideReport)
+    where
+        I: IntoIterator,
+    {
+        ratios::validate(ratios).unwrap_or_else(|e| panic!("{}", e));
+
+        let mut samples_iter = samples.into_iter();
+        let samples_iter = SizedIter::new(&mut samples_iter);
+        let samples_len = samples_iter.size_hint().1.unwrap();
+        let sizes = self.ratios_to_sizes(ratios, samples_len);
+        let report = DivideReport::new(ratios, &sizes, samples_len);
+        (self.divide_by_size(samples_iter, &sizes), report)
+    }
+
     /// Group a slice of samples, with specifying the sizes of each group.
     ///
     /// Compared to [`divide_by_size`](Self::divide_by_size), this method
     /// can only be used for slices. However, it generally runs faster when
     /// [`stable`](Self::stable) is `false`.
     ///
+    /// Samples are distinguished by position, not by value. Therefore, equal
+    /// samples can be placed into different groups. To keep them together,
+    /// use [`divide_slice_by_size_eq`](Self::divide_slice_by_size_eq)
+    /// (this method panics if [`treat_equal_as_same`](Self::treat_equal_as_same)
+    /// is `true`).
+    ///
     /// Behavior of this method is affected by following values.
     ///
     /// * Random number generator and its seed (at construction).
     /// * Orders inside each groups (See [`stable`](Self::stable)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
     ///
     /// # Panics
     ///
@@ -217,14 +636,34 @@ impl<'r> RandomGrouping<'r> {
         samples: &'t [T],
         sizes: &[usize],
     ) -> Vec<Vec<&'t T>> {
+        self.check_by_position();
+        self.divide_slice_by_position(samples, sizes)
+    }
+
+    /// Group a slice of samples by position, with specifying the sizes of each group.
+    ///
+    /// This is body of [`divide_slice_by_size`](Self::divide_slice_by_size),
+    /// without checking [`treat_equal_as_same`](Self::treat_equal_as_same).
+    fn divide_slice_by_position<'t, T>(
+        &mut self,
+        samples: &'t [T],
+        sizes: &[usize],
+    ) -> Vec<Vec<&'t T>> {
+        self.last_timing = None;
+        self.check_groups_spec(sizes.len(), samples.len());
+
         if samples.len() < sizes.iter().sum() {
             panic!("Samples length is greater than sizes total.");
         }
 
         let (len, amount) = (samples.len(), sizes.iter().sum::<usize>());
-        let mut idxs = sample(&mut *self.rng, len, amount).into_vec();
+        let mut idxs = self.select(len, amount);
         let mut results = Vec::with_capacity(sizes.len());
 
+        if self.global_stable {
+            idxs.sort_unstable();
+        }
+
         for (lower, upper) in sizes.iter().cloned().trace2(0, |total, size| total + size) {
             let group_range = lower..upper;
             let group_item_idxs = sort_if(self.stable, &mut idxs[group_range]);
@@ -253,73 +692,936 @@ impl<'r> RandomGrouping<'r> {
         }
     }
 
-    /// Group a slice of samples, with specifying the ratios of each group.
+    /// Group a slice of samples into owned clones, with specifying the sizes
+    /// of each group.
     ///
-    /// Compared to [`divide_by_ratio`](Self::divide_by_ratio), this method
-    /// can only be used for slices. However, it generally runs faster when
-    /// [`stable`](Self::stable) is `false`.
+    /// Groups are same as [`divide_slice_by_size`](Self::divide_slice_by_size),
+    /// but each group holds clones instead of references. Only the selected
+    /// samples are cloned, so unselected samples are never touched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the samples length is less than group size total.
+    pub fn divide_slice_by_size_cloned<T: Clone>(
+        &mut self,
+        samples: &[T],
+        sizes: &[usize],
+    ) -> Vec<Vec<T>> {
+        let groups = self.divide_slice_by_size(samples, sizes);
+        let clone_all = |group: Vec<&T>| group.into_iter().cloned().collect();
+        groups.into_iter().map(clone_all).collect()
+    }
+
+    /// Group a slice of samples, with specifying the sizes of each group and
+    /// marking empty groups.
+    ///
+    /// Groups are same as [`divide_slice_by_size`](Self::divide_slice_by_size),
+    /// but each group with zero size is `None`, and each other group is `Some`.
+    /// Therefore, intentionally empty groups can be distinguished.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the samples length is less than group size total.
+    pub fn divide_by_size_optional<'t, T>(
+        &mut self,
+        samples: &'t [T],
+        sizes: &[usize],
+    ) -> Vec<Option<Vec<&'t T>>> {
+        let groups = self.divide_slice_by_size(samples, sizes);
+        let to_option = |(group, &size)| (size > 0).then_some(group);
+        groups.into_iter().zip(sizes).map(to_option).collect()
+    }
+
+    /// Group a slice of samples, with specifying the sizes of each group and
+    /// considering equality of samples.
+    ///
+    /// If [`treat_equal_as_same`](Self::treat_equal_as_same) is `true`,
+    /// samples with equal values are treated as one unit, and all samples of
+    /// a value are placed into the same group, in their original order. In
+    /// this case, `sizes` count distinct values, not samples. For example, a
+    /// group of size 2 can hold samples `[a, a, b]`. Otherwise, this is same
+    /// as [`divide_slice_by_size`](Self::divide_slice_by_size), and `sizes`
+    /// count samples.
     ///
     /// Behavior of this method is affected by following values.
     ///
     /// * Random number generator and its seed (at construction).
+    /// * Equality of samples (See [`treat_equal_as_same`](Self::treat_equal_as_same)).
     /// * Orders inside each groups (See [`stable`](Self::stable)).
-    /// * Rounding strategy for group size (See [`rounding`](Self::rounding)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
     ///
     /// # Panics
     ///
-    /// Panics in the following cases.
-    ///
-    /// * Ratios contains NaN.
-    /// * Ratios contains infinite value.
-    /// * Ratios contains negative value.
-    /// * Ratios summary is greater than 1.
-    pub fn divide_slice_by_ratio<'t, T>(
+    /// Panics if the samples length (or distinct values count, if equal
+    /// samples are treated as same) is less than group size total.
+    pub fn divide_slice_by_size_eq<'t, T>(
         &mut self,
         samples: &'t [T],
-        ratios: &[f64],
-    ) -> Vec<Vec<&'t T>> {
-        if !ratios.iter().all(Self::check_ratio) {
-            panic!("Ratios contains illegal value.");
+        sizes: &[usize],
+    ) -> Vec<Vec<&'t T>>
+    where
+        T: Eq + Hash,
+    {
+        if !self.treat_equal_as_same {
+            return self.divide_slice_by_position(samples, sizes);
         }
 
-        if ratios.iter().sum::<f64>() > 1.0 {
-            panic!("Ratios total is greater than 1.");
+        let mut units = Vec::<Vec<&'t T>>::new();
+        let mut unit_idxs = HashMap::<&T, usize>::new();
+        for sample in samples {
+            match unit_idxs.entry(sample) {
+                Entry::Occupied(e) => units[*e.get()].push(sample),
+                Entry::Vacant(e) => {
+                    e.insert(units.len());
+                    units.push(vec![sample]);
+                }
+            }
         }
 
-        let sizes = self.ratios_to_sizes(ratios, samples.len());
-        self.divide_by_size(samples, &sizes)
+        let unit_groups = self.divide_slice_by_position(&units, sizes);
+        let group_items = |group: Vec<&Vec<&'t T>>| group.into_iter().flatten().copied().collect();
+        unit_groups.into_iter().map(group_items).collect()
     }
 
-    /// Returns `true` if given value is valid as ratio.
-    fn check_ratio(x: &f64) -> bool {
-        !x.is_nan() && *x >= 0.0 && x.is_finite()
-    }
+    /// Group a slice of samples, with specifying the sizes of each group, by
+    /// placing each drawn sample into the least full group.
+    ///
+    /// Samples are drawn one by one at random, and each of them is placed into
+    /// the group with the lowest fill rate (filled count per size) among groups
+    /// not yet full. If there are several such groups, the first one is chosen.
+    /// Therefore, groups are kept balanced throughout the filling. Final sizes
+    /// are same as `sizes`. [`global_stable`](Self::global_stable) is ignored,
+    /// because group of each sample depends on the drawn order.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Random number generator and its seed (at construction).
+    /// * Orders inside each groups (See [`stable`](Self::stable)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the samples length is less than group size total.
+    pub fn divide_by_size_least_full<'t, T>(
+        &mut self,
+        samples: &'t [T],
+        sizes: &[usize],
+    ) -> Vec<Vec<&'t T>> {
+        self.check_by_position();
+        self.last_timing = None;
+        self.check_groups_spec(sizes.len(), samples.len());
 
-    /// Convert group ratios to group sizes with total length and rounding strategy.
-    fn ratios_to_sizes(&self, ratios: &[f64], len: usize) -> Vec<usize> {
-        return match self.rounding() {
-            SizeRounding::Floor => floor(ratios, len),
-            SizeRounding::Tail => tail(ratios, len),
-            SizeRounding::Each => each(ratios, len),
-        };
+        if samples.len() < sizes.iter().sum() {
+            panic!("Samples length is greater than sizes total.");
+        }
+
+        let (len, amount) = (samples.len(), sizes.iter().sum::<usize>());
+        let idxs = self.select(len, amount);
+        let mut groups = sizes
+            .iter()
+            .map(|&x| Vec::with_capacity(x))
+            .collect::<Vec<_>>();
+        let mut open_groups = (0..sizes.len())
+            .filter(|&g| sizes[g] > 0)
+            .map(|g| FillRate {
+                index: g,
+                len: 0,
+                size: sizes[g],
+            })
+            .collect::<BinaryHeap<_>>();
 
-        fn floor(ratios: &[f64], len: usize) -> Vec<usize> {
-            let results = ratios.iter().map(|x| (x * len as f64).floor() as usize);
-            results.collect()
+        for idx in idxs {
+            let mut target = open_groups.pop().unwrap();
+            groups[target.index].push(idx);
+            target.len += 1;
+            if target.len < target.size {
+                open_groups.push(target);
+            }
         }
 
-        fn tail(ratios: &[f64], len: usize) -> Vec<usize> {
-            let sizes = ratios.iter().map(|x| (x * len as f64).round() as usize);
-            let points = sizes.trace(0, |&s, x| (s + x).min(len));
-            let results = points.diff(0, |c, p| c - p);
-            results.collect()
+        let mut results = Vec::with_capacity(sizes.len());
+        for mut group in groups {
+            if self.stable {
+                group.sort();
+            }
+
+            results.push(group.into_iter().map(|idx| &samples[idx]).collect());
         }
 
-        fn each(ratios: &[f64], len: usize) -> Vec<usize> {
-            let points = ratios.iter().trace(0.0, |&s, x| s + x);
-            let points = points.map(move |x| (x * len as f64).round() as usize);
-            let results = points.diff(0, |c, p| c - p);
-            results.collect()
+        results
+    }
+
+    /// Group a slice of samples, with specifying the sizes of each group,
+    /// in a cancellable way.
+    ///
+    /// Groups are same as [`divide_slice_by_size`](Self::divide_slice_by_size).
+    /// But `should_cancel` is called periodically while samples are placed
+    /// into groups, and if it returns `true`, this method stops and returns
+    /// `None`. This is useful for very large samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the samples length is less than group size total.
+    pub fn divide_by_size_cancellable<'t, T, F>(
+        &mut self,
+        samples: &'t [T],
+        sizes: &[usize],
+        should_cancel: F,
+    ) -> Option<Vec<Vec<&'t T>>>
+    where
+        F: Fn() -> bool,
+    {
+        self.check_by_position();
+        self.last_timing = None;
+        self.check_groups_spec(sizes.len(), samples.len());
+
+        if samples.len() < sizes.iter().sum() {
+            panic!("Samples length is greater than sizes total.");
+        }
+
+        let (len, amount) = (samples.len(), sizes.iter().sum::<usize>());
+        let mut idxs = self.select(len, amount);
+        let mut results = Vec::with_capacity(sizes.len());
+        let mut placed = 0;
+
+        if self.global_stable {
+            idxs.sort_unstable();
+        }
+
+        for (lower, upper) in sizes.iter().cloned().trace2(0, |total, size| total + size) {
+            let group_item_idxs = &mut idxs[lower..upper];
+            if self.stable {
+                group_item_idxs.sort();
+            }
+
+            let mut group_items = Vec::with_capacity(group_item_idxs.len());
+            for &idx in group_item_idxs.iter() {
+                if placed % CANCEL_CHECK_INTERVAL == 0 && should_cancel() {
+                    return None;
+                }
+
+                group_items.push(&samples[idx]);
+                placed += 1;
+            }
+
+            results.push(group_items);
+        }
+
+        Some(results)
+    }
+
+    /// Group a slice of samples, with specifying the sizes of each group and
+    /// the weights of each sample.
+    ///
+    /// Groups are filled in order by weighted random sampling without
+    /// replacement. Therefore, heavier samples are more likely to be placed
+    /// into earlier groups. Samples with zero weight are placed only after
+    /// all others are placed, in random order. Group sizes are same as `sizes`.
+    /// [`global_stable`](Self::global_stable) is ignored, because group of
+    /// each sample depends on the drawn order.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Random number generator and its seed (at construction).
+    /// * Orders inside each groups (See [`stable`](Self::stable)).
+    ///
+    /// # Panics
+    ///
+    /// Panics in the following cases.
+    ///
+    /// * The samples length is less than group size total.
+    /// * The weights length is not equal to the samples length.
+    /// * Weights contains NaN, infinite or negative value.
+    pub fn divide_by_size_weighted_strata<'t, T>(
+        &mut self,
+        samples: &'t [T],
+        sizes: &[usize],
+        weights: &[f64],
+    ) -> Vec<Vec<&'t T>> {
+        self.check_by_position();
+        self.last_timing = None;
+        self.check_groups_spec(sizes.len(), samples.len());
+
+        if samples.len() < sizes.iter().sum() {
+            panic!("Samples length is greater than sizes total.");
+        }
+
+        if weights.len() != samples.len() {
+            panic!("Weights length is not equal to samples length.");
+        }
+
+        if !weights.iter().all(Self::check_ratio) {
+            panic!("Weights contains illegal value.");
+        }
+
+        // Efraimidis-Spirakis keys (in log scale), larger is earlier.
+        // Ties (such as zero weights) are broken by another random key.
+        let mut keyed_idxs = Vec::with_capacity(weights.len());
+        for (idx, &weight) in weights.iter().enumerate() {
+            let key = self.rng.gen::<f64>().ln() / weight;
+            let sub_key = self.rng.gen::<u64>();
+            keyed_idxs.push((key, sub_key, idx));
+        }
+
+        keyed_idxs.sort_by(|x, y| y.0.total_cmp(&x.0).then(y.1.cmp(&x.1)));
+
+        let mut idxs = keyed_idxs.into_iter().map(|x| x.2).collect::<Vec<_>>();
+        let mut results = Vec::with_capacity(sizes.len());
+
+        for (lower, upper) in sizes.iter().cloned().trace2(0, |total, size| total + size) {
+            let group_item_idxs = &mut idxs[lower..upper];
+            if self.stable {
+                group_item_idxs.sort();
+            }
+
+            results.push(group_item_idxs.iter().map(|&idx| &samples[idx]).collect());
+        }
+
+        results
+    }
+
+    /// Returns group labels of samples, with specifying the sizes of each group.
+    ///
+    /// Result has `len` elements, and the element at index `i` is the group
+    /// index of the `i`-th sample. Samples not placed in any group are
+    /// labeled [`UNPLACED`]. Grouping is same as
+    /// [`divide_slice_by_size`](Self::divide_slice_by_size) (when called
+    /// with same random number generator state).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is less than group size total.
+    pub fn group_labels_by_size(&mut self, len: usize, sizes: &[usize]) -> Vec<usize> {
+        self.check_by_position();
+        self.last_timing = None;
+        self.check_groups_spec(sizes.len(), len);
+
+        if len < sizes.iter().sum() {
+            panic!("Samples length is greater than sizes total.");
+        }
+
+        let amount = sizes.iter().sum::<usize>();
+        let mut idxs = self.select(len, amount);
+        if self.global_stable {
+            idxs.sort_unstable();
+        }
+
+        let group_areas = sizes.iter().cloned().trace2(0, |total, size| total + size);
+        let mut results = vec![UNPLACED; len];
+
+        for (group_idx, (lower, upper)) in group_areas.enumerate() {
+            for &idx in &idxs[lower..upper] {
+                results[idx] = group_idx;
+            }
+        }
+
+        results
+    }
+
+    /// Repeat grouping and summarize a statistic of each result.
+    ///
+    /// Samples are grouped `runs` times by
+    /// [`divide_slice_by_size`](Self::divide_slice_by_size), and `stat` is
+    /// applied to each result. The random number generator advances at
+    /// each grouping, so each result is different.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the following cases.
+    ///
+    /// * The samples length is less than group size total.
+    /// * `runs` is zero.
+    pub fn repeat_stats<'t, T, F>(
+        &mut self,
+        samples: &'t [T],
+        sizes: &[usize],
+        runs: usize,
+        stat: F,
+    ) -> StatsSummary
+    where
+        F: Fn(&[Vec<&'t T>]) -> f64,
+    {
+        if runs == 0 {
+            panic!("Runs is zero.");
+        }
+
+        let mut values = Vec::with_capacity(runs);
+        for _ in 0..runs {
+            let groups = self.divide_slice_by_size(samples, sizes);
+            values.push(stat(&groups));
+        }
+
+        StatsSummary::from_values(&values)
+    }
+
+    /// Find the seed giving the best grouping among candidate seeds.
+    ///
+    /// For each seed of `seeds`, samples are grouped by
+    /// [`divide_slice_by_size`](Self::divide_slice_by_size) with an instance
+    /// made by [`clone_config`](Self::clone_config), and `score` is applied
+    /// to the result. Returns the seed with the highest score and the score.
+    /// If there are several such seeds, the first one is returned. Seeds
+    /// with NaN score are skipped.
+    ///
+    /// The random number generator of this instance is never used, so its
+    /// state is same as before the call. To reproduce the best grouping,
+    /// use [`clone_config`](Self::clone_config) with the returned seed.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the following cases.
+    ///
+    /// * The samples length is less than group size total.
+    /// * `seeds` is empty, or all scores are NaN.
+    pub fn best_seed_by<'t, T, I, F>(
+        &self,
+        samples: &'t [T],
+        sizes: &[usize],
+        seeds: I,
+        score: F,
+    ) -> (u64, f64)
+    where
+        I: IntoIterator<Item = u64>,
+        F: Fn(&[Vec<&'t T>]) -> f64,
+    {
+        let mut best = None::<(u64, f64)>;
+        for seed in seeds {
+            let groups = self.clone_config(seed).divide_slice_by_size(samples, sizes);
+            let value = score(&groups);
+            if value.is_nan() {
+                continue;
+            }
+
+            match best {
+                Some((_, best_value)) if value <= best_value => {}
+                _ => best = Some((seed, value)),
+            }
+        }
+
+        best.expect("Seeds is empty or all scores are NaN.")
+    }
+
+    /// Group a slice of samples, with specifying the ratios of each group.
+    ///
+    /// Compared to [`divide_by_ratio`](Self::divide_by_ratio), this method
+    /// can only be used for slices. However, it generally runs faster when
+    /// [`stable`](Self::stable) is `false`.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Random number generator and its seed (at construction).
+    /// * Orders inside each groups (See [`stable`](Self::stable)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
+    /// * Rounding strategy for group size (See [`rounding`](Self::rounding)).
+    /// * Integer ratio math flag (See [`integer_ratio_math`](Self::integer_ratio_math)).
+    ///
+    /// # Panics
+    ///
+    /// Panics in the following cases.
+    ///
+    /// * Ratios contains NaN.
+    /// * Ratios contains infinite value.
+    /// * Ratios contains negative value.
+    /// * Ratios summary is greater than 1.
+    pub fn divide_slice_by_ratio<'t, T>(
+        &mut self,
+        samples: &'t [T],
+        ratios: &[f64],
+    ) -> Vec<Vec<&'t T>> {
+        ratios::validate(ratios).unwrap_or_else(|e| panic!("{}", e));
+
+        let sizes = self.ratios_to_sizes(ratios, samples.len());
+        self.divide_by_size(samples, &sizes)
+    }
+
+    /// Group a slice of samples, with specifying the basis points of each group.
+    ///
+    /// Basis point is 1/10000 of the whole. Compared to
+    /// [`divide_slice_by_ratio`](Self::divide_slice_by_ratio), this method
+    /// calculates group sizes with integer arithmetic only, so there is no
+    /// floating point error.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Random number generator and its seed (at construction).
+    /// * Orders inside each groups (See [`stable`](Self::stable)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
+    /// * Rounding strategy for group size (See [`rounding`](Self::rounding)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if basis points total is greater than 10000.
+    pub fn divide_by_basis_points<'t, T>(
+        &mut self,
+        samples: &'t [T],
+        bps: &[u32],
+    ) -> Vec<Vec<&'t T>> {
+        if bps.iter().map(|&x| x as u64).sum::<u64>() > BPS_WHOLE {
+            panic!("Basis points total is greater than 10000.");
+        }
+
+        let sizes = self.bps_to_sizes(bps, samples.len());
+        self.divide_slice_by_size(samples, &sizes)
+    }
+
+    /// Group a slice of samples, with specifying the fractions of remaining
+    /// samples for each group.
+    ///
+    /// Each fraction applies to the samples not yet placed into preceding
+    /// groups, and group size is its result with floor operation. For example,
+    /// `[0.5, 0.5]` for 10 samples makes groups of size 5 and 2.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Random number generator and its seed (at construction).
+    /// * Orders inside each groups (See [`stable`](Self::stable)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
+    ///
+    /// # Panics
+    ///
+    /// Panics in the following cases.
+    ///
+    /// * Fractions contains NaN.
+    /// * Fractions contains infinite value.
+    /// * Fractions contains negative value.
+    /// * Fractions contains value greater than 1.
+    pub fn divide_by_remaining_fractions<'t, T>(
+        &mut self,
+        samples: &'t [T],
+        fracs: &[f64],
+    ) -> Vec<Vec<&'t T>> {
+        if !fracs.iter().all(|x| Self::check_ratio(x) && *x <= 1.0) {
+            panic!("Fractions contains illegal value.");
+        }
+
+        let mut sizes = Vec::with_capacity(fracs.len());
+        let mut rest = samples.len();
+
+        for frac in fracs {
+            let size = (frac * rest as f64).floor() as usize;
+            sizes.push(size);
+            rest -= size;
+        }
+
+        self.divide_slice_by_size(samples, &sizes)
+    }
+
+    /// Group a slice of samples, with specifying the cumulative sizes of
+    /// each group.
+    ///
+    /// Each value is the total size of the group and all preceding groups,
+    /// so group size is the difference from the preceding value. For example,
+    /// `[9, 19, 30]` makes groups of size 9, 10 and 11.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Random number generator and its seed (at construction).
+    /// * Orders inside each groups (See [`stable`](Self::stable)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
+    ///
+    /// # Panics
+    ///
+    /// Panics in the following cases.
+    ///
+    /// * Cumulative sizes is decreasing somewhere.
+    /// * The samples length is less than the last cumulative size.
+    pub fn divide_by_cumulative_sizes<'t, T>(
+        &mut self,
+        samples: &'t [T],
+        cumulative: &[usize],
+    ) -> Vec<Vec<&'t T>> {
+        if cumulative.windows(2).any(|w| w[0] > w[1]) {
+            panic!("Cumulative sizes is decreasing.");
+        }
+
+        let sizes = cumulative
+            .iter()
+            .cloned()
+            .diff(0, |c, p| c - p)
+            .collect::<Vec<_>>();
+        self.divide_slice_by_size(samples, &sizes)
+    }
+
+    /// Append new samples into existing groups, with specifying the added
+    /// sizes of each group.
+    ///
+    /// New samples are grouped by
+    /// [`divide_slice_by_size`](Self::divide_slice_by_size) with `add_sizes`,
+    /// and each result is appended to the group of `partition` at the same
+    /// position. Existing members of groups are kept as is.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the following cases.
+    ///
+    /// * `add_sizes` length is not same as `partition` length.
+    /// * The new samples length is less than added size total.
+    pub fn extend_partition<'t, T>(
+        &mut self,
+        partition: &mut Vec<Vec<&'t T>>,
+        new_samples: &'t [T],
+        add_sizes: &[usize],
+    ) {
+        if partition.len() != add_sizes.len() {
+            panic!("Added sizes length is not same as groups count.");
+        }
+
+        let added_groups = self.divide_slice_by_size(new_samples, add_sizes);
+        for (group, added_group) in partition.iter_mut().zip(added_groups) {
+            group.extend(added_group);
+        }
+    }
+
+    /// Group a slice of samples by blocks, with specifying the sizes of each
+    /// group in blocks.
+    ///
+    /// Samples are split into contiguous blocks of `block_len` samples (the
+    /// last block can be shorter), and each block is placed into a group as
+    /// an atomic unit. Therefore, samples in the same block always belong to
+    /// the same group, and keep their original order inside the block.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Random number generator and its seed (at construction).
+    /// * Orders of blocks inside each groups (See [`stable`](Self::stable)).
+    /// * Orders of blocks across groups (See [`global_stable`](Self::global_stable)).
+    ///
+    /// # Panics
+    ///
+    /// Panics in the following cases.
+    ///
+    /// * `block_len` is zero.
+    /// * The blocks count is less than group size total.
+    pub fn divide_blocks_by_size<'t, T>(
+        &mut self,
+        samples: &'t [T],
+        block_len: usize,
+        sizes: &[usize],
+    ) -> Vec<Vec<&'t T>> {
+        if block_len == 0 {
+            panic!("Block length is zero.");
+        }
+
+        let blocks = samples.chunks(block_len).collect::<Vec<_>>();
+        let block_groups = self.divide_slice_by_size(&blocks, sizes);
+        let group_items = |group: Vec<&&'t [T]>| group.into_iter().flat_map(|&x| x).collect();
+        block_groups.into_iter().map(group_items).collect()
+    }
+
+    /// Group a slice of samples into bins, with specifying the maximum size of bins.
+    ///
+    /// The number of bins is the minimum number required to hold all samples.
+    /// All bins except the last one are filled up to `max_per_bin`, and the
+    /// last one holds the rest.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Random number generator and its seed (at construction).
+    /// * Orders inside each groups (See [`stable`](Self::stable)).
+    /// * Orders across groups (See [`global_stable`](Self::global_stable)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_per_bin` is zero.
+    pub fn divide_into_bins<'t, T>(
+        &mut self,
+        samples: &'t [T],
+        max_per_bin: usize,
+    ) -> Vec<Vec<&'t T>> {
+        if max_per_bin == 0 {
+            panic!("Max per bin is zero.");
+        }
+
+        let (full_bins, rest) = (samples.len() / max_per_bin, samples.len() % max_per_bin);
+        let mut sizes = vec![max_per_bin; full_bins];
+        if rest > 0 {
+            sizes.push(rest);
+        }
+
+        self.divide_slice_by_size(samples, &sizes)
+    }
+
+    /// Generate group sizes from a distribution.
+    ///
+    /// Each size is sampled from `dist` and rounded. Sizes less than 1 are
+    /// raised to 1, and the last size is trimmed so that sizes total is
+    /// equal to `total`. Random numbers are taken from the random number
+    /// generator of this instance.
+    ///
+    /// This method is available with `rand_distr` feature.
+    #[cfg(feature = "rand_distr")]
+    pub fn random_sizes_from<D>(&mut self, total: usize, dist: D) -> Vec<usize>
+    where
+        D: Distribution<f64>,
+    {
+        let mut results = Vec::new();
+        let mut rest = total;
+
+        while rest > 0 {
+            let size = (dist.sample(&mut *self.rng).round() as usize).clamp(1, rest);
+            results.push(size);
+            rest -= size;
+        }
+
+        results
+    }
+
+    /// Write group sizes for the specified ratios and samples length into buffer.
+    ///
+    /// Group sizes are same as those used in
+    /// [`divide_by_ratio`](Self::divide_by_ratio). This method does not
+    /// allocate memory. It takes `&mut self` because
+    /// [`Random`](TieBreak::Random) tie-breaking consumes random numbers.
+    ///
+    /// Behavior of this method is affected by following values.
+    ///
+    /// * Rounding strategy for group size (See [`rounding`](Self::rounding)).
+    /// * Integer ratio math flag (See [`integer_ratio_math`](Self::integer_ratio_math)).
+    ///
+    /// # Panics
+    ///
+    /// Panics in the following cases.
+    ///
+    /// * Ratios length is not equal to `out` length.
+    /// * Ratios contains NaN.
+    /// * Ratios contains infinite value.
+    /// * Ratios contains negative value.
+    /// * Ratios summary is greater than 1.
+    pub fn ratios_to_sizes_into(&mut self, ratios: &[f64], len: usize, out: &mut [usize]) {
+        if ratios.len() != out.len() {
+            panic!("Ratios length is not equal to output length.");
+        }
+
+        ratios::validate(ratios).unwrap_or_else(|e| panic!("{}", e));
+
+        self.write_sizes(ratios, len, out);
+    }
+
+    /// Select sample indices with sampling strategy.
+    fn select(&mut self, population: usize, amount: usize) -> Vec<usize> {
+        let results = self.sampling.select(&mut *self.rng, population, amount);
+        if results.len() != amount {
+            panic!("Sampling strategy selected wrong amount.");
+        }
+
+        let mut selected = HashSet::with_capacity(amount);
+        for &idx in &results {
+            if idx >= population {
+                panic!("Sampling strategy selected index out of range.");
+            }
+            if !selected.insert(idx) {
+                panic!("Sampling strategy selected duplicate index.");
+            }
+        }
+
+        results
+    }
+
+    /// Check that [`treat_equal_as_same`](Self::treat_equal_as_same) flag is not set.
+    fn check_by_position(&self) {
+        if self.treat_equal_as_same {
+            panic!("Equal samples can be treated as same only by `divide_slice_by_size_eq`.");
+        }
+    }
+
+    /// Check group specification with [`require_groups`](Self::require_groups) flag.
+    fn check_groups_spec(&self, groups_len: usize, samples_len: usize) {
+        if self.require_groups && groups_len == 0 && samples_len > 0 {
+            panic!("{}", GroupingError::NoGroupsSpecified);
+        }
+    }
+
+    /// Record timing of phases, if [`timing`](Self::timing) flag is set.
+    fn record_timing(
+        &mut self,
+        started: Option<Instant>,
+        sampled: Option<Instant>,
+        gathered: Option<Instant>,
+    ) {
+        if let (Some(started), Some(sampled), Some(gathered)) = (started, sampled, gathered) {
+            let shuffled = Instant::now();
+            let sampling = sampled - started;
+            let gathering = gathered - sampled;
+            let shuffling = shuffled - gathered;
+            self.last_timing = Some(Timing::new(sampling, gathering, shuffling));
+        }
+    }
+
+    /// Returns `true` if given value is valid as ratio.
+    fn check_ratio(x: &f64) -> bool {
+        !x.is_nan() && *x >= 0.0 && x.is_finite()
+    }
+
+    /// Convert group ratios to group sizes with total length and rounding strategy.
+    fn ratios_to_sizes(&mut self, ratios: &[f64], len: usize) -> Vec<usize> {
+        let mut results = vec![0; ratios.len()];
+        self.write_sizes(ratios, len, &mut results);
+        results
+    }
+
+    /// Write group sizes converted from group ratios into buffer.
+    fn write_sizes(&mut self, ratios: &[f64], len: usize, out: &mut [usize]) {
+        if self.integer_ratio_math {
+            let part = |i: usize| (ratios[i] * RATIO_WHOLE as f64).round() as u64;
+            return self.write_part_sizes(part, RATIO_WHOLE, len, out);
+        }
+
+        return match self.rounding() {
+            SizeRounding::Floor => floor(ratios, len, out),
+            SizeRounding::Tail => tail(ratios, len, out),
+            SizeRounding::Each => each(ratios, len, out),
+            SizeRounding::LargestRemainder => {
+                let exact = |i: usize| split_exact(ratios[i] * len as f64);
+                let total = (ratios.iter().sum::<f64>() * len as f64).round() as usize;
+                for (i, size) in out.iter_mut().enumerate() {
+                    *size = exact(i).0;
+                }
+
+                self.distribute_remainder(out, exact, total.min(len));
+            }
+        };
+
+        fn floor(ratios: &[f64], len: usize, out: &mut [usize]) {
+            for (ratio, size) in ratios.iter().zip(out) {
+                *size = (ratio * len as f64).floor() as usize;
+            }
+        }
+
+        fn tail(ratios: &[f64], len: usize, out: &mut [usize]) {
+            let mut prev_point = 0;
+            for (ratio, size) in ratios.iter().zip(out) {
+                let point = (prev_point + (ratio * len as f64).round() as usize).min(len);
+                *size = point - prev_point;
+                prev_point = point;
+            }
+        }
+
+        fn each(ratios: &[f64], len: usize, out: &mut [usize]) {
+            let (mut total, mut prev_point) = (0.0, 0);
+            for (ratio, size) in ratios.iter().zip(out) {
+                total += ratio;
+                let point = (total * len as f64).round() as usize;
+                *size = point - prev_point;
+                prev_point = point;
+            }
+        }
+
+        fn split_exact(x: f64) -> (usize, f64) {
+            (x.floor() as usize, x - x.floor())
+        }
+    }
+
+    /// Convert group basis points to group sizes with total length and rounding strategy.
+    fn bps_to_sizes(&mut self, bps: &[u32], len: usize) -> Vec<usize> {
+        let mut results = vec![0; bps.len()];
+        self.write_part_sizes(|i| bps[i] as u64, BPS_WHOLE, len, &mut results);
+        results
+    }
+
+    /// Write group sizes converted from integer parts of the whole into buffer.
+    ///
+    /// `part` returns numerator of each group ratio, and `whole` is their
+    /// common denominator. All calculations are done with integers.
+    fn write_part_sizes<P>(&mut self, part: P, whole: u64, len: usize, out: &mut [usize])
+    where
+        P: Fn(usize) -> u64,
+    {
+        return match self.rounding() {
+            SizeRounding::Floor => floor(part, whole, len, out),
+            SizeRounding::Tail => tail(part, whole, len, out),
+            SizeRounding::Each => each(part, whole, len, out),
+            SizeRounding::LargestRemainder => {
+                let exact = |i: usize| part(i) as u128 * len as u128;
+                let exact = |i: usize| {
+                    (
+                        (exact(i) / whole as u128) as usize,
+                        exact(i) % whole as u128,
+                    )
+                };
+                let total = mul_round((0..out.len()).map(&part).sum(), whole, len);
+                for (i, size) in out.iter_mut().enumerate() {
+                    *size = exact(i).0;
+                }
+
+                self.distribute_remainder(out, exact, total.min(len));
+            }
+        };
+
+        fn floor<P: Fn(usize) -> u64>(part: P, whole: u64, len: usize, out: &mut [usize]) {
+            for (i, size) in out.iter_mut().enumerate() {
+                *size = mul_floor(part(i), whole, len);
+            }
+        }
+
+        fn tail<P: Fn(usize) -> u64>(part: P, whole: u64, len: usize, out: &mut [usize]) {
+            let mut prev_point = 0;
+            for (i, size) in out.iter_mut().enumerate() {
+                let point = (prev_point + mul_round(part(i), whole, len)).min(len);
+                *size = point - prev_point;
+                prev_point = point;
+            }
+        }
+
+        fn each<P: Fn(usize) -> u64>(part: P, whole: u64, len: usize, out: &mut [usize]) {
+            let (mut total, mut prev_point) = (0, 0);
+            for (i, size) in out.iter_mut().enumerate() {
+                total += part(i);
+                let point = mul_round(total, whole, len).min(len);
+                *size = point - prev_point;
+                prev_point = point;
+            }
+        }
+
+        fn mul_floor(part: u64, whole: u64, len: usize) -> usize {
+            (part as u128 * len as u128 / whole as u128) as usize
+        }
+
+        fn mul_round(part: u64, whole: u64, len: usize) -> usize {
+            let half = whole as u128 / 2;
+            ((part as u128 * len as u128 + half) / whole as u128) as usize
+        }
+    }
+
+    /// Distribute leftover samples to groups in descending order of fractional part.
+    ///
+    /// `sizes` must be filled with truncated group sizes, `exact` returns
+    /// truncated size and fractional part of each group, and `total` is group
+    /// size total after distribution. This method does not allocate memory.
+    fn distribute_remainder<R, F>(&mut self, sizes: &mut [usize], exact: F, total: usize)
+    where
+        R: PartialOrd + Copy,
+        F: Fn(usize) -> (usize, R),
+    {
+        let leftover = total.saturating_sub(sizes.iter().sum());
+
+        for _ in 0..leftover {
+            let mut best = None::<(usize, R)>;
+            let mut ties = 0;
+
+            for (idx, &size) in sizes.iter().enumerate() {
+                let (floor, frac) = exact(idx);
+                if size != floor {
+                    continue;
+                }
+
+                match best {
+                    Some((_, best_frac)) if frac < best_frac => {}
+                    Some((_, best_frac)) if frac == best_frac => {
+                        ties += 1;
+                        if self.tiebreak == TieBreak::Random && self.rng.gen_range(0..ties) == 0 {
+                            best = Some((idx, frac));
+                        }
+                    }
+                    _ => {
+                        best = Some((idx, frac));
+                        ties = 1;
+                    }
+                }
+            }
+
+            match best {
+                Some((idx, _)) => sizes[idx] += 1,
+                None => break,
+            }
         }
     }
 }
@@ -328,8 +1630,16 @@ impl Default for RandomGrouping<'_> {
     fn default() -> Self {
         Self {
             stable: true,
+            global_stable: false,
             rounding: SizeRounding::Floor,
-            rng: Staff::new_own(Box::new(Pcg32::seed_from_u64(0))),
+            tiebreak: TieBreak::ByIndex,
+            sampling: Arc::new(UniformSampling),
+            require_groups: false,
+            treat_equal_as_same: false,
+            integer_ratio_math: false,
+            timing: false,
+            last_timing: None,
+            rng: Staff::new_own(Box::new(Pcg32::seed_from_u64(DEFAULT_SEED))),
         }
     }
 }
@@ -0,0 +1,123 @@
+use crate::grouping_error::GroupingError;
+
+/// Validated group ratios.
+///
+/// Values are checked once at construction, so that
+/// [`divide_by_valid_ratio`](crate::RandomGrouping::divide_by_valid_ratio)
+/// can skip checking them at each grouping.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ratios(Vec<f64>);
+
+impl Ratios {
+    /// Create an instance from ratio values.
+    ///
+    /// # Errors
+    ///
+    /// Returns error in the following cases.
+    ///
+    /// * [`NanRatio`](GroupingError::NanRatio) - values contains NaN.
+    /// * [`InfiniteRatio`](GroupingError::InfiniteRatio) - values contains infinite value.
+    /// * [`NegativeRatio`](GroupingError::NegativeRatio) - values contains negative value.
+    /// * [`RatioTotalOver`](GroupingError::RatioTotalOver) - values total is greater than 1.
+    pub fn new(values: Vec<f64>) -> Result<Self, GroupingError> {
+        validate(&values)?;
+        Ok(Self(values))
+    }
+
+    /// Returns ratio values.
+    #[must_use]
+    pub fn as_slice(&self) -> &[f64] {
+        &self.0
+    }
+
+    /// Returns ratio values, with consuming this instance.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<f64> {
+        self.0
+    }
+}
+
+impl AsRef<[f64]> for Ratios {
+    fn as_ref(&self) -> &[f64] {
+        self.as_slice()
+    }
+}
+
+impl TryFrom<Vec<f64>> for Ratios {
+    type Error = GroupingError;
+
+    fn try_from(values: Vec<f64>) -> Result<Self, Self::Error> {
+        Self::new(values)
+    }
+}
+
+/// Create ratios from relative weights, with normalizing them.
+///
+/// Each ratio is the weight divided by weights total. So, ratios total is
+/// 1 unless weights are empty. If floating point error makes ratios total
+/// greater than 1, the excess is subtracted from the largest ratio. Therefore,
+/// result always satisfies the same conditions as [`Ratios::new`].
+///
+/// # Errors
+///
+/// Returns error in the following cases.
+///
+/// * [`NanRatio`](GroupingError::NanRatio) - weights contains NaN.
+/// * [`InfiniteRatio`](GroupingError::InfiniteRatio) - weights contains infinite value.
+/// * [`NegativeRatio`](GroupingError::NegativeRatio) - weights contains negative value.
+/// * [`ZeroWeightSum`](GroupingError::ZeroWeightSum) - weights are not empty but their total is zero.
+/// * [`InfiniteWeightSum`](GroupingError::InfiniteWeightSum) - weights total overflows.
+pub fn normalize_ratios(weights: &[f64]) -> Result<Ratios, GroupingError> {
+    validate_each(weights)?;
+
+    let total = weights.iter().sum::<f64>();
+    if total == 0.0 && !weights.is_empty() {
+        return Err(GroupingError::ZeroWeightSum);
+    } else if total.is_infinite() {
+        return Err(GroupingError::InfiniteWeightSum);
+    }
+
+    let mut results = weights.iter().map(|x| x / total).collect::<Vec<_>>();
+    loop {
+        let excess = results.iter().sum::<f64>() - 1.0;
+        if excess <= 0.0 {
+            break;
+        }
+
+        let max = results.iter_mut().max_by(|x, y| x.total_cmp(y)).unwrap();
+        let reduced = *max - excess;
+        *max = if reduced < *max {
+            reduced.max(0.0)
+        } else {
+            f64::from_bits(max.to_bits() - 1)
+        };
+    }
+
+    Ok(Ratios(results))
+}
+
+/// Check ratio values.
+pub(crate) fn validate(values: &[f64]) -> Result<(), GroupingError> {
+    validate_each(values)?;
+
+    if values.iter().sum::<f64>() > 1.0 {
+        return Err(GroupingError::RatioTotalOver);
+    }
+
+    Ok(())
+}
+
+/// Check each ratio value.
+fn validate_each(values: &[f64]) -> Result<(), GroupingError> {
+    for &value in values {
+        if value.is_nan() {
+            return Err(GroupingError::NanRatio);
+        } else if value.is_infinite() {
+            return Err(GroupingError::InfiniteRatio);
+        } else if value < 0.0 {
+            return Err(GroupingError::NegativeRatio);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use rand::seq::index::sample;
+use rand::RngCore;
+
+/// Strategy to select samples for grouping.
+///
+/// Grouping methods select sample indices with this trait, then assign them
+/// to groups in order. For example, with sizes `[2, 3]`, the first 2 indices
+/// go to the first group and the next 3 indices go to the second group.
+/// Therefore, the order of selected indices decides group membership.
+///
+/// Following methods bypass this trait.
+///
+/// * [`divide_by_size_weighted_strata`](crate::RandomGrouping::divide_by_size_weighted_strata)
+///   (samples are drawn by their weights).
+/// * [`divide_by_size`](crate::RandomGrouping::divide_by_size) and methods
+///   based on it, with a single group holding all samples.
+///
+/// See also [`with_sampling_strategy`](crate::RandomGrouping::with_sampling_strategy).
+pub trait SamplingStrategy: Send + Sync {
+    /// Select `amount` distinct indices from `0..population`.
+    ///
+    /// Grouping methods panic if returned indices break this rule.
+    fn select(&self, rng: &mut dyn RngCore, population: usize, amount: usize) -> Vec<usize>;
+}
+
+/// Uniform random sampling.
+///
+/// This is the default strategy. All indices are selected with the same
+/// probability, and they are returned in random order.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct UniformSampling;
+
+impl SamplingStrategy for UniformSampling {
+    fn select(&self, rng: &mut dyn RngCore, population: usize, amount: usize) -> Vec<usize> {
+        sample(rng, population, amount).into_vec()
+    }
+}
@@ -5,6 +5,7 @@
 /// size is almost the result of multiplying samples length and group ratio.
 /// But it is real number therefore rounding to `usize` is required.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum SizeRounding {
     /// Size is calculated with floor operation.
     ///
@@ -23,4 +24,13 @@ pub enum SizeRounding {
     /// Pros: Group size totals can be controlled.<br/>
     /// Cons: Even If ratios are equal, result sizes could be not equal.
     Each,
+
+    /// Leftover samples are given to groups with largest fractional parts.
+    ///
+    /// Pros: Each size differs from its exact value by less than 1.<br/>
+    /// Cons: Even If ratios are equal, result sizes could be not equal.
+    ///
+    /// Ties of fractional parts are broken by
+    /// [`remainder_tiebreak`](crate::RandomGrouping::remainder_tiebreak).
+    LargestRemainder,
 }
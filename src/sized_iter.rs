@@ -36,20 +36,30 @@ impl<'a, T> Iterator for SizedIter<'a, T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.src_iter.is_some() {
-            self.src_iter.as_mut().unwrap().next()
-        } else if self.vec_iter.is_some() {
-            self.vec_iter.as_mut().unwrap().next()
+        if let Some(iter) = self.src_iter.as_mut() {
+            iter.next()
+        } else if let Some(iter) = self.vec_iter.as_mut() {
+            iter.next()
+        } else {
+            panic!();
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if let Some(iter) = self.src_iter.as_mut() {
+            iter.nth(n)
+        } else if let Some(iter) = self.vec_iter.as_mut() {
+            iter.nth(n)
         } else {
             panic!();
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        if self.src_iter.is_some() {
-            self.src_iter.as_ref().unwrap().size_hint()
-        } else if self.vec_iter.is_some() {
-            self.vec_iter.as_ref().unwrap().size_hint()
+        if let Some(iter) = self.src_iter.as_ref() {
+            iter.size_hint()
+        } else if let Some(iter) = self.vec_iter.as_ref() {
+            iter.size_hint()
         } else {
             panic!();
         }
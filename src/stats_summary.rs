@@ -0,0 +1,57 @@
+/// Summary statistics of repeated groupings.
+///
+/// This struct is returned by
+/// [`repeat_stats`](crate::RandomGrouping::repeat_stats) method.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StatsSummary {
+    /// Arithmetic mean.
+    mean: f64,
+    /// Minimum value.
+    min: f64,
+    /// Maximum value.
+    max: f64,
+    /// Population standard deviation.
+    stddev: f64,
+}
+
+impl StatsSummary {
+    /// Create an instance from values.
+    pub(crate) fn from_values(values: &[f64]) -> Self {
+        let len = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / len;
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        Self {
+            mean,
+            min,
+            max,
+            stddev: variance.sqrt(),
+        }
+    }
+
+    /// Returns arithmetic mean.
+    #[must_use]
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns minimum value.
+    #[must_use]
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Returns maximum value.
+    #[must_use]
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Returns population standard deviation.
+    #[must_use]
+    pub fn stddev(&self) -> f64 {
+        self.stddev
+    }
+}
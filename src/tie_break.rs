@@ -0,0 +1,14 @@
+/// Tie-breaking strategy for remainder distribution.
+///
+/// This enum is used in [`LargestRemainder`](crate::SizeRounding::LargestRemainder)
+/// rounding strategy. If several groups have the same fractional part, it
+/// decides which of them receive leftover samples.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TieBreak {
+    /// Groups with lower index win.
+    ByIndex,
+
+    /// Groups are chosen at random (with random number generator of grouping).
+    Random,
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+/// Timing breakdown of grouping.
+///
+/// This struct is returned by
+/// [`last_timing`](crate::RandomGrouping::last_timing) method.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Timing {
+    /// Time to select samples.
+    sampling: Duration,
+    /// Time to gather selected samples into groups.
+    gathering: Duration,
+    /// Time to shuffle samples inside groups.
+    shuffling: Duration,
+}
+
+impl Timing {
+    /// Create an instance from durations of each phase.
+    pub(crate) fn new(sampling: Duration, gathering: Duration, shuffling: Duration) -> Self {
+        Self {
+            sampling,
+            gathering,
+            shuffling,
+        }
+    }
+
+    /// Returns time to select samples.
+    #[must_use]
+    pub fn sampling(&self) -> Duration {
+        self.sampling
+    }
+
+    /// Returns time to gather selected samples into groups.
+    #[must_use]
+    pub fn gathering(&self) -> Duration {
+        self.gathering
+    }
+
+    /// Returns time to shuffle samples inside groups.
+    ///
+    /// This is almost zero if [`stable`](crate::RandomGrouping::stable) is `true`.
+    #[must_use]
+    pub fn shuffling(&self) -> Duration {
+        self.shuffling
+    }
+}
@@ -2,25 +2,60 @@ mod for_test;
 
 use crate::for_test::helper::*;
 use crate::for_test::samples::*;
+use rand::distributions::Distribution;
+use rand::seq::index::sample;
+use rand::Rng;
+use rand::RngCore;
+#[cfg(feature = "rand_distr")]
+use rand_distr::Uniform;
+use random_grouping::FrozenGrouping;
+use random_grouping::GroupingError;
 use random_grouping::RandomGrouping;
+use random_grouping::Ratios;
+use random_grouping::SamplingStrategy;
 use random_grouping::SizeRounding;
+use random_grouping::TieBreak;
+use random_grouping::DEFAULT_SEED;
+use random_grouping::UNPLACED;
+use static_assertions::assert_impl_all;
+use std::cell::Cell;
 use std::iter;
+use std::sync::Arc;
 use test_panic::*;
 
 #[test]
 fn new() {
     let mut result = RandomGrouping::new();
 
-    assert_eq!(result.stable(), true);
+    assert!(result.stable());
+    assert!(!result.global_stable());
     assert_eq!(result.rounding(), SizeRounding::Floor);
+    assert_eq!(result.remainder_tiebreak(), TieBreak::ByIndex);
+    assert!(!result.require_groups());
+    assert!(!result.treat_equal_as_same());
+    assert!(!result.integer_ratio_math());
+    assert!(!result.timing());
     assert!(check_target(&mut result));
 }
 
+#[test]
+fn new_with_default_seed() {
+    let mut target_x = RandomGrouping::new().with_stable(false);
+    let mut target_y = RandomGrouping::from_seed(DEFAULT_SEED).with_stable(false);
+    let samples = create_samples();
+    let sizes = create_just_group_sizes();
+
+    let results_x = target_x.divide_by_size(&samples, &sizes);
+    let results_y = target_y.divide_by_size(&samples, &sizes);
+
+    assert_eq!(results_x, results_y);
+}
+
 #[test]
 fn auto_seed() {
     let mut result = RandomGrouping::auto_seed();
 
-    assert_eq!(result.stable(), true);
+    assert!(result.stable());
     assert_eq!(result.rounding(), SizeRounding::Floor);
     assert!(check_target(&mut result));
 }
@@ -29,210 +64,2584 @@ fn auto_seed() {
 fn from_seed() {
     let mut result = RandomGrouping::from_seed(42);
 
-    assert_eq!(result.stable(), true);
+    assert!(result.stable());
     assert_eq!(result.rounding(), SizeRounding::Floor);
     assert!(check_target(&mut result));
 }
 
+#[test]
+fn from_seed_bytes() {
+    with_default();
+    with_dup();
+    with_other_key();
+    with_fixed_hash();
+
+    fn with_default() {
+        let mut result = RandomGrouping::from_seed_bytes(b"experiment-42");
+
+        assert!(result.stable());
+        assert_eq!(result.rounding(), SizeRounding::Floor);
+        assert!(check_target(&mut result));
+    }
+
+    fn with_dup() {
+        let mut target_x = RandomGrouping::from_seed_bytes(b"experiment-42");
+        let mut target_y = RandomGrouping::from_seed_bytes(b"experiment-42");
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results_x = target_x.divide_by_size(&samples, &sizes);
+        let results_y = target_y.divide_by_size(&samples, &sizes);
+
+        assert_eq!(results_x, results_y);
+    }
+
+    fn with_other_key() {
+        let mut target_x = RandomGrouping::from_seed_bytes(b"experiment-42");
+        let mut target_y = RandomGrouping::from_seed_bytes(b"experiment-43");
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results_x = target_x.divide_by_size(&samples, &sizes);
+        let results_y = target_y.divide_by_size(&samples, &sizes);
+
+        assert_ne!(results_x, results_y);
+    }
+
+    fn with_fixed_hash() {
+        let keys_and_hashes = [
+            (&b""[..], 0xcbf29ce484222325),
+            (&b"a"[..], 0xaf63dc4c8601ec8c),
+            (&b"foobar"[..], 0x85944171f73967e8),
+        ];
+
+        for (key, hash) in keys_and_hashes {
+            let mut target_x = RandomGrouping::from_seed_bytes(key);
+            let mut target_y = RandomGrouping::from_seed(hash);
+            let samples = create_samples();
+            let sizes = create_just_group_sizes();
+
+            let results_x = target_x.divide_by_size(&samples, &sizes);
+            let results_y = target_y.divide_by_size(&samples, &sizes);
+
+            assert_eq!(results_x, results_y);
+        }
+    }
+}
+
 #[test]
 fn from_rng() {
     let mut rng = create_rng();
 
     let mut result = RandomGrouping::from_rng(&mut rng);
 
-    assert_eq!(result.stable(), true);
+    assert!(result.stable());
     assert_eq!(result.rounding(), SizeRounding::Floor);
     assert!(check_target(&mut result));
 }
 
 #[test]
-fn divide_by_size() {
-    with_zero_groups();
-    with_samples_gt_group_totals();
-    with_samples_eq_group_totals();
-    with_samples_lt_group_totals();
-    with_stable();
-    with_dup();
-    with_empty_group();
+fn with_remainder_tiebreak() {
+    with_by_index();
+    with_random();
 
-    fn with_zero_groups() {
-        let mut target = create_target();
+    fn with_by_index() {
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = vec![1.0 / 3.0; 3];
+
+        for seed in 0..100 {
+            let mut target = RandomGrouping::from_seed(seed)
+                .with_rounding(SizeRounding::LargestRemainder)
+                .with_remainder_tiebreak(TieBreak::ByIndex);
+
+            let results = target.divide_by_ratio(&samples, &ratios);
+
+            let sizes = results.iter().map(|x| x.len()).collect::<Vec<_>>();
+            assert_eq!(sizes, vec![4, 3, 3]);
+        }
+    }
+
+    fn with_random() {
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = vec![1.0 / 3.0; 3];
+        let mut counts = [0; 3];
+
+        for seed in 0..300 {
+            let mut target = RandomGrouping::from_seed(seed)
+                .with_rounding(SizeRounding::LargestRemainder)
+                .with_remainder_tiebreak(TieBreak::Random);
+
+            let results = target.divide_by_ratio(&samples, &ratios);
+
+            let sizes = results.iter().map(|x| x.len()).collect::<Vec<_>>();
+            let winner = sizes.iter().position(|&x| x == 4).unwrap();
+            assert_eq!(sizes.iter().sum::<usize>(), 10);
+            counts[winner] += 1;
+        }
+
+        assert!(counts.iter().all(|&x| (70..=130).contains(&x)));
+    }
+}
+
+#[test]
+fn with_sampling_strategy() {
+    with_divide_by_size();
+    with_divide_slice_by_size();
+    with_clone_config();
+    with_freeze();
+    with_wrong_amount();
+    with_duplicate_index();
+    with_index_out_of_range();
+    with_whole_group();
+
+    fn with_divide_by_size() {
+        let mut target = create_target().with_sampling_strategy(Box::new(HeadSampling));
         let samples = create_samples();
-        let sizes = Vec::<usize>::new();
+        let sizes = create_small_group_sizes();
 
         let results = target.divide_by_size(&samples, &sizes);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert_eq!(results, head_groups(&samples, &sizes));
     }
 
-    fn with_samples_gt_group_totals() {
-        let mut target = create_target();
+    fn with_divide_slice_by_size() {
+        let mut target = create_target().with_sampling_strategy(Box::new(HeadSampling));
         let samples = create_samples();
         let sizes = create_small_group_sizes();
 
-        let results = target.divide_by_size(&samples, &sizes);
+        let results = target.divide_slice_by_size(&samples, &sizes);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert_eq!(results, head_groups(&samples, &sizes));
     }
 
-    fn with_samples_eq_group_totals() {
-        let mut target = create_target();
+    fn with_clone_config() {
+        let target = create_target().with_sampling_strategy(Box::new(HeadSampling));
         let samples = create_samples();
-        let sizes = create_just_group_sizes();
+        let sizes = create_small_group_sizes();
 
-        let results = target.divide_by_size(&samples, &sizes);
+        let results = target
+            .clone_config(42)
+            .divide_slice_by_size(&samples, &sizes);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert_eq!(results, head_groups(&samples, &sizes));
     }
 
-    fn with_samples_lt_group_totals() {
-        let mut target = create_target();
+    fn with_freeze() {
+        let target = create_target().with_sampling_strategy(Box::new(HeadSampling));
         let samples = create_samples();
-        let sizes = create_large_group_sizes();
+        let sizes = create_small_group_sizes();
+
+        let results = target.freeze(42).split_by_size(&samples, &sizes);
+
+        assert_eq!(results, head_groups(&samples, &sizes));
+    }
+
+    fn with_wrong_amount() {
+        let mut target = create_target().with_sampling_strategy(Box::new(EmptySampling));
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
 
         let result = test_panic(|| {
+            target.divide_slice_by_size(&samples, &sizes);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_duplicate_index() {
+        let mut target = create_target().with_sampling_strategy(Box::new(ZeroSampling));
+        let samples = create_samples();
+        let sizes = [2, 3];
+
+        let result_x = test_panic(|| {
+            target.divide_by_size(&samples, &sizes);
+        });
+        let result_y = test_panic(|| {
+            target.divide_slice_by_size(&samples, &sizes);
+        });
+        let result_z = test_panic(|| {
+            target.group_labels_by_size(samples.len(), &sizes);
+        });
+
+        assert!(result_x.is_panic());
+        assert!(result_y.is_panic());
+        assert!(result_z.is_panic());
+    }
+
+    fn with_index_out_of_range() {
+        let mut target = create_target().with_sampling_strategy(Box::new(OverSampling));
+        let samples = create_samples();
+        let sizes = [2, 3];
+
+        let result_x = test_panic(|| {
             target.divide_by_size(&samples, &sizes);
         });
+        let result_y = test_panic(|| {
+            target.divide_slice_by_size(&samples, &sizes);
+        });
+
+        assert!(result_x.is_panic());
+        assert!(result_y.is_panic());
+    }
+
+    fn with_whole_group() {
+        let mut target = create_target().with_sampling_strategy(Box::new(EmptySampling));
+        let samples = create_samples();
+        let sizes = [samples.len()];
+
+        let results = target.divide_by_size(&samples, &sizes);
+
+        assert_eq!(results, head_groups(&samples, &sizes));
+    }
+
+    fn head_groups<'t>(samples: &'t [i32], sizes: &[usize]) -> Vec<Vec<&'t i32>> {
+        let mut iter = samples.iter();
+        let groups = sizes.iter().map(|&size| iter.by_ref().take(size).collect());
+        groups.collect()
+    }
+
+    struct HeadSampling;
+
+    impl SamplingStrategy for HeadSampling {
+        fn select(&self, _: &mut dyn RngCore, _: usize, amount: usize) -> Vec<usize> {
+            (0..amount).collect()
+        }
+    }
+
+    struct EmptySampling;
+
+    impl SamplingStrategy for EmptySampling {
+        fn select(&self, _: &mut dyn RngCore, _: usize, _: usize) -> Vec<usize> {
+            vec![]
+        }
+    }
+
+    struct ZeroSampling;
+
+    impl SamplingStrategy for ZeroSampling {
+        fn select(&self, _: &mut dyn RngCore, _: usize, amount: usize) -> Vec<usize> {
+            vec![0; amount]
+        }
+    }
+
+    struct OverSampling;
+
+    impl SamplingStrategy for OverSampling {
+        fn select(&self, _: &mut dyn RngCore, population: usize, amount: usize) -> Vec<usize> {
+            (population..population + amount).collect()
+        }
+    }
+}
+
+#[test]
+fn with_require_groups() {
+    with_strict();
+    with_strict_empty_samples();
+    with_strict_ratio();
+    with_strict_freeze();
+    with_permissive();
+
+    fn with_strict() {
+        let mut target = create_target().with_require_groups(true);
+        let samples = create_samples();
+
+        let result_x = test_panic(|| {
+            target.divide_by_size(&samples, &[]);
+        });
+        let result_y = test_panic(|| {
+            target.divide_slice_by_size(&samples, &[]);
+        });
+
+        let message = GroupingError::NoGroupsSpecified.to_string();
+        assert_eq!(result_x.message(), message);
+        assert_eq!(result_y.message(), message);
+    }
+
+    fn with_strict_empty_samples() {
+        let mut target = create_target().with_require_groups(true);
+        let samples = Vec::<i32>::new();
+
+        let results = target.divide_by_size(&samples, &[]);
+
+        assert!(results.is_empty());
+    }
+
+    fn with_strict_ratio() {
+        let mut target = create_target().with_require_groups(true);
+        let samples = create_samples();
+
+        let result = test_panic(|| {
+            target.divide_by_ratio(&samples, &[]);
+        });
 
         assert!(result.is_panic());
     }
 
-    fn with_stable() {
-        let mut target = create_target().with_stable(true);
+    fn with_strict_freeze() {
+        let frozen = create_target().with_require_groups(true).freeze(42);
         let samples = create_samples();
-        let sizes = create_just_group_sizes();
+
+        let result = test_panic(|| {
+            frozen.split_by_size(&samples, &[]);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_permissive() {
+        let mut target = create_target().with_require_groups(false);
+        let samples = create_samples();
+
+        let results_x = target.divide_by_size(&samples, &[]);
+        let results_y = target.divide_by_ratio(&samples, &[]);
+
+        assert!(results_x.is_empty());
+        assert!(results_y.is_empty());
+    }
+}
+
+#[test]
+fn with_global_stable() {
+    with_divide_by_size();
+    with_divide_slice_by_size();
+    with_whole_group();
+    with_cancellable();
+    with_group_labels();
+    with_freeze();
+
+    fn with_divide_by_size() {
+        let mut target = create_target().with_stable(false).with_global_stable(true);
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
 
         let results = target.divide_by_size(&samples, &sizes);
 
-        assert!(results.iter().all(|x| is_group_stable(x, &samples)));
+        assert!(check_groups(&results, &sizes, &samples));
+        assert!(is_flatten_stable(&results));
     }
 
-    fn with_dup() {
-        let mut target_x = create_target();
-        let mut target_y = create_target();
+    fn with_divide_slice_by_size() {
+        let mut target = create_target().with_stable(false).with_global_stable(true);
         let samples = create_samples();
         let sizes = create_just_group_sizes();
 
-        let results_x = target_x.divide_by_size(&samples, &sizes);
-        let results_y = target_y.divide_by_size(&samples, &sizes);
+        let results = target.divide_slice_by_size(&samples, &sizes);
 
-        assert_eq!(results_x, results_y);
+        assert!(check_groups(&results, &sizes, &samples));
+        assert!(is_flatten_stable(&results));
     }
 
-    fn with_empty_group() {
-        let mut target = create_target();
+    fn with_whole_group() {
+        let mut target = create_target().with_stable(false).with_global_stable(true);
         let samples = create_samples();
-        let sizes = create_group_sizes_with_some_empty();
+        let sizes = [samples.len()];
 
         let results = target.divide_by_size(&samples, &sizes);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert_eq!(results, [samples.iter().collect::<Vec<_>>()]);
+    }
+
+    fn with_freeze() {
+        let frozen = create_target()
+            .with_stable(false)
+            .with_global_stable(true)
+            .freeze(42);
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+
+        let results = frozen.split_by_size(&samples, &sizes);
+
+        assert!(is_flatten_stable(&results));
+    }
+
+    fn with_cancellable() {
+        let target = create_target().with_stable(false).with_global_stable(true);
+        let mut target_x = target.clone_config(42);
+        let mut target_y = target.clone_config(42);
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+
+        let results_x = target_x.divide_by_size_cancellable(&samples, &sizes, || false);
+        let results_y = target_y.divide_slice_by_size(&samples, &sizes);
+
+        assert!(is_flatten_stable(results_x.as_ref().unwrap()));
+        assert_eq!(results_x.unwrap(), results_y);
+    }
+
+    fn with_group_labels() {
+        let target = create_target().with_stable(false).with_global_stable(true);
+        let mut target_x = target.clone_config(42);
+        let mut target_y = target.clone_config(42);
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+
+        let labels = target_x.group_labels_by_size(samples.len(), &sizes);
+        let results = target_y.divide_slice_by_size(&samples, &sizes);
+
+        for (group_idx, group) in results.iter().enumerate() {
+            assert!(group.iter().all(|&&x| labels[x as usize] == group_idx));
+        }
+    }
+
+    fn is_flatten_stable(groups: &[Vec<&i32>]) -> bool {
+        let flatten = groups.iter().flatten().collect::<Vec<_>>();
+        flatten.windows(2).all(|w| w[0] < w[1])
     }
 }
 
 #[test]
-fn divide_by_ratio() {
-    with_zero_groups();
-    with_samples_gt_group_totals();
-    with_samples_eq_group_totals();
-    with_samples_lt_group_totals();
-    with_empty_group();
-    with_nan_ratio_group();
-    with_infinite_ratio_group();
-    with_negative_ratio_group();
-    with_stable();
-    with_rounding_floor();
-    with_rounding_tail();
-    with_rounding_each();
+fn with_integer_ratio_math() {
+    with_typical_ratios();
+    with_divide_by_ratio();
+    with_floor_boundary();
+    with_round_boundary();
+    with_finer_precision();
+
+    fn with_typical_ratios() {
+        let roundings = [
+            SizeRounding::Tail,
+            SizeRounding::Each,
+            SizeRounding::LargestRemainder,
+        ];
+        let ratios_list = [
+            vec![0.5, 0.5],
+            vec![0.3, 0.3, 0.2],
+            vec![0.25, 0.25, 0.25, 0.25],
+            vec![0.1, 0.2, 0.3, 0.4],
+        ];
+
+        for rounding in roundings {
+            let mut target_x = create_target().with_rounding(rounding);
+            let mut target_y = target_x.clone_config(0).with_integer_ratio_math(true);
+            for ratios in &ratios_list {
+                for len in 0..=200 {
+                    let mut sizes_x = vec![0; ratios.len()];
+                    let mut sizes_y = vec![0; ratios.len()];
+                    target_x.ratios_to_sizes_into(ratios, len, &mut sizes_x);
+                    target_y.ratios_to_sizes_into(ratios, len, &mut sizes_y);
+                    assert_eq!(sizes_x, sizes_y);
+                }
+            }
+        }
+    }
 
-    fn with_zero_groups() {
-        let mut target = create_target();
+    fn with_divide_by_ratio() {
+        let mut target = create_target().with_integer_ratio_math(true);
         let samples = create_samples();
-        let sizes = Vec::<usize>::new();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
+        let ratios = [0.3, 0.3, 0.2];
 
         let results = target.divide_by_ratio(&samples, &ratios);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert!(check_groups(&results, &[9, 9, 6], &samples));
     }
 
-    fn with_samples_gt_group_totals() {
-        let mut target = create_target();
+    fn with_floor_boundary() {
+        let mut target_x = create_target().with_integer_ratio_math(false);
+        let mut target_y = create_target().with_integer_ratio_math(true);
+        let (mut sizes_x, mut sizes_y) = ([0], [0]);
+
+        target_x.ratios_to_sizes_into(&[0.29], 100, &mut sizes_x);
+        target_y.ratios_to_sizes_into(&[0.29], 100, &mut sizes_y);
+
+        assert_eq!(sizes_x, [28]);
+        assert_eq!(sizes_y, [29]);
+    }
+
+    fn with_round_boundary() {
+        let target = create_target().with_rounding(SizeRounding::LargestRemainder);
+        let mut target_x = target.clone_config(0).with_integer_ratio_math(false);
+        let mut target_y = target.clone_config(0).with_integer_ratio_math(true);
+        let (mut sizes_x, mut sizes_y) = ([0; 3], [0; 3]);
+
+        target_x.ratios_to_sizes_into(&[0.7, 0.2, 0.1], 2, &mut sizes_x);
+        target_y.ratios_to_sizes_into(&[0.7, 0.2, 0.1], 2, &mut sizes_y);
+
+        assert_eq!(sizes_x, [1, 1, 0]);
+        assert_eq!(sizes_y, [2, 0, 0]);
+    }
+
+    fn with_finer_precision() {
+        let mut target_x = create_target().with_integer_ratio_math(false);
+        let mut target_y = create_target().with_integer_ratio_math(true);
+        let (mut sizes_x, mut sizes_y) = ([0], [0]);
+
+        target_x.ratios_to_sizes_into(&[1e-10], 10_000_000_000, &mut sizes_x);
+        target_y.ratios_to_sizes_into(&[1e-10], 10_000_000_000, &mut sizes_y);
+
+        assert_eq!(sizes_x, [1]);
+        assert_eq!(sizes_y, [0]);
+    }
+}
+
+#[test]
+fn with_timing() {
+    with_enabled();
+    with_enabled_whole_group();
+    with_disabled();
+    with_other_method();
+    with_clone_config();
+
+    fn with_enabled() {
+        let mut target = create_target().with_timing(true);
         let samples = create_samples();
-        let sizes = create_small_group_sizes();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
+        let sizes = create_just_group_sizes();
 
-        let results = target.divide_by_ratio(&samples, &ratios);
+        assert_eq!(target.last_timing(), None);
+        target.divide_by_size(&samples, &sizes);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert!(target.last_timing().is_some());
     }
 
-    fn with_samples_eq_group_totals() {
-        let mut target = create_target();
+    fn with_enabled_whole_group() {
+        let mut target = create_target().with_timing(true);
+        let samples = create_samples();
+        let sizes = [samples.len()];
+
+        target.divide_by_size(&samples, &sizes);
+
+        assert!(target.last_timing().is_some());
+    }
+
+    fn with_disabled() {
+        let mut target = create_target().with_timing(false);
         let samples = create_samples();
         let sizes = create_just_group_sizes();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
 
-        let results = target.divide_by_ratio(&samples, &ratios);
+        target.divide_by_size(&samples, &sizes);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert_eq!(target.last_timing(), None);
     }
 
-    fn with_samples_lt_group_totals() {
+    fn with_other_method() {
+        let mut target = create_target().with_timing(true);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        target.divide_by_size(&samples, &sizes);
+        target.divide_slice_by_size(&samples, &sizes);
+
+        assert_eq!(target.last_timing(), None);
+    }
+
+    fn with_clone_config() {
+        let mut target = create_target().with_timing(true);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        target.divide_by_size(&samples, &sizes);
+        let result = target.clone_config(42);
+
+        assert!(result.timing());
+        assert_eq!(result.last_timing(), None);
+    }
+}
+
+#[test]
+fn clone_config() {
+    with_settings();
+    with_borrowed_rng();
+    with_dup();
+    with_other_seed();
+
+    fn with_settings() {
+        let target = create_target()
+            .with_stable(false)
+            .with_rounding(SizeRounding::Each)
+            .with_treat_equal_as_same(true)
+            .with_integer_ratio_math(true)
+            .with_global_stable(true);
+
+        let result = target.clone_config(42);
+
+        assert!(!result.stable());
+        assert_eq!(result.rounding(), SizeRounding::Each);
+        assert!(result.treat_equal_as_same());
+        assert!(result.integer_ratio_math());
+        assert!(result.global_stable());
+    }
+
+    fn with_borrowed_rng() {
+        let mut rng = create_rng();
+        let target = RandomGrouping::from_rng(&mut rng).with_stable(false);
+
+        let mut result = target.clone_config(42);
+
+        assert!(!result.stable());
+        assert!(check_target(&mut result));
+    }
+
+    fn with_dup() {
+        let target = create_target();
+        let mut target_x = target.clone_config(42);
+        let mut target_y = RandomGrouping::from_seed(42);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results_x = target_x.divide_by_size(&samples, &sizes);
+        let results_y = target_y.divide_by_size(&samples, &sizes);
+
+        assert_eq!(results_x, results_y);
+    }
+
+    fn with_other_seed() {
+        let target = create_target().with_stable(false);
+        let mut target_x = target.clone_config(1);
+        let mut target_y = target.clone_config(2);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results_x = target_x.divide_by_size(&samples, &sizes);
+        let results_y = target_y.divide_by_size(&samples, &sizes);
+
+        assert_eq!(target_x.stable(), target_y.stable());
+        assert_eq!(target_x.rounding(), target_y.rounding());
+        assert_ne!(results_x, results_y);
+    }
+}
+
+#[test]
+fn freeze() {
+    with_settings();
+    with_dup();
+    with_repeat();
+    with_shared();
+
+    fn with_settings() {
+        let target = create_target().with_stable(false).with_global_stable(true);
+
+        let result = target.freeze(42);
+
+        assert!(!result.stable());
+        assert!(result.global_stable());
+        assert_eq!(result.seed(), 42);
+    }
+
+    fn with_dup() {
+        let frozen_x = create_target().freeze(42);
+        let frozen_y = create_target().freeze(42);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results_x = [0; 3].map(|_| frozen_x.split_by_size(&samples, &sizes));
+        let results_y = [0; 3].map(|_| frozen_y.split_by_size(&samples, &sizes));
+
+        assert_eq!(results_x, results_y);
+    }
+
+    fn with_repeat() {
+        let frozen = create_target().freeze(42);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results_x = frozen.split_by_size(&samples, &sizes);
+        let results_y = frozen.split_by_size(&samples, &sizes);
+
+        assert!(check_groups(&results_x, &sizes, &samples));
+        assert!(check_groups(&results_y, &sizes, &samples));
+        assert_ne!(results_x, results_y);
+    }
+
+    fn with_shared() {
+        assert_impl_all!(FrozenGrouping: Send, Sync);
+        let frozen = Arc::new(create_target().freeze(42));
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results = frozen.split_by_size(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+}
+
+#[test]
+fn group_index_distribution() {
+    with_proportions();
+    with_empty_group();
+    with_zero_total();
+
+    fn with_proportions() {
+        let target = create_target();
+        let sizes = create_just_group_sizes();
+        let total = sizes.iter().sum::<usize>();
+        let mut rng = create_rng();
+
+        let dist = target.group_index_distribution(&sizes);
+
+        let draws = 30000;
+        let mut counts = vec![0; sizes.len()];
+        for idx in (&mut rng).sample_iter(&dist).take(draws) {
+            counts[idx] += 1;
+        }
+
+        for (&count, &size) in counts.iter().zip(&sizes) {
+            let actual = count as f64 / draws as f64;
+            let expected = size as f64 / total as f64;
+            assert!((actual - expected).abs() < 0.02);
+        }
+    }
+
+    fn with_empty_group() {
+        let target = create_target();
+        let sizes = create_group_sizes_with_some_empty();
+        let mut rng = create_rng();
+
+        let dist = target.group_index_distribution(&sizes);
+
+        assert!((0..1000).all(|_| dist.sample(&mut rng) != 1));
+    }
+
+    fn with_zero_total() {
+        let target = create_target();
+        let sizes = vec![0, 0];
+
+        let result = test_panic(|| {
+            let _ = target.group_index_distribution(&sizes);
+        });
+
+        assert!(result.is_panic());
+    }
+}
+
+#[test]
+fn divide_by_size() {
+    with_zero_groups();
+    with_samples_gt_group_totals();
+    with_samples_eq_group_totals();
+    with_samples_lt_group_totals();
+    with_stable();
+    with_dup();
+    with_empty_group();
+    with_large_index();
+    with_whole_group();
+    with_whole_group_unstable();
+    with_whole_group_same_as_general();
+
+    fn with_zero_groups() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = Vec::<usize>::new();
+
+        let results = target.divide_by_size(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_gt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+
+        let results = target.divide_by_size(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_eq_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results = target.divide_by_size(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_large_group_sizes();
+
+        let result = test_panic(|| {
+            target.divide_by_size(&samples, &sizes);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_stable() {
+        let mut target = create_target().with_stable(true);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results = target.divide_by_size(&samples, &sizes);
+
+        assert!(results.iter().all(|x| is_group_stable(x, &samples)));
+    }
+
+    fn with_dup() {
+        let mut target_x = create_target();
+        let mut target_y = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results_x = target_x.divide_by_size(&samples, &sizes);
+        let results_y = target_y.divide_by_size(&samples, &sizes);
+
+        assert_eq!(results_x, results_y);
+    }
+
+    fn with_empty_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_group_sizes_with_some_empty();
+
+        let results = target.divide_by_size(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_large_index() {
+        let mut target = create_target();
+        let samples = 0..usize::MAX;
+        let sizes = create_small_group_sizes();
+
+        let results = target.divide_by_size(samples, &sizes);
+
+        let all = results.iter().flatten().collect::<Vec<_>>();
+        assert!(results.iter().zip(&sizes).all(|(g, &s)| g.len() == s));
+        assert!(results.iter().all(|g| g.windows(2).all(|w| w[0] < w[1])));
+        assert!(all.iter().any(|&&x| x > isize::MAX as usize));
+    }
+
+    fn with_whole_group() {
+        let mut target = create_target().with_stable(true);
+        let samples = create_samples();
+        let sizes = [samples.len()];
+
+        let results = target.divide_by_size(&samples, &sizes);
+
+        assert_eq!(results, vec![samples.iter().collect::<Vec<_>>()]);
+    }
+
+    fn with_whole_group_unstable() {
+        let mut target = create_target().with_stable(false);
+        let samples = create_samples();
+        let sizes = [samples.len()];
+
+        let results = target.divide_by_size(&samples, &sizes);
+
+        let mut sorted = results[0].clone();
+        sorted.sort();
+        assert!(check_groups(&results, &sizes, &samples));
+        assert_eq!(sorted, samples.iter().collect::<Vec<_>>());
+    }
+
+    fn with_whole_group_same_as_general() {
+        let mut target_x = create_target().with_stable(true);
+        let mut target_y = create_target().with_stable(true);
+        let samples = create_samples();
+        let sizes_x = [samples.len()];
+        let sizes_y = [samples.len(), 0];
+
+        let results_x = target_x.divide_by_size(&samples, &sizes_x);
+        let results_y = target_y.divide_by_size(&samples, &sizes_y);
+
+        assert_eq!(results_x[0], results_y[0]);
+    }
+}
+
+#[test]
+fn divide_by_ratio() {
+    with_zero_groups();
+    with_empty_samples();
+    with_samples_gt_group_totals();
+    with_samples_eq_group_totals();
+    with_samples_lt_group_totals();
+    with_empty_group();
+    with_nan_ratio_group();
+    with_infinite_ratio_group();
+    with_negative_ratio_group();
+    with_stable();
+    with_rounding_floor();
+    with_rounding_tail();
+    with_rounding_each();
+    with_rounding_largest_remainder();
+
+    fn with_zero_groups() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = Vec::<usize>::new();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let results = target.divide_by_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_empty_samples() {
+        let roundings = [
+            SizeRounding::Floor,
+            SizeRounding::Tail,
+            SizeRounding::Each,
+            SizeRounding::LargestRemainder,
+        ];
+        for rounding in roundings {
+            let mut target = create_target().with_rounding(rounding);
+            let samples = Vec::<i32>::new();
+            let ratios = [0.3, 0.3, 0.4];
+
+            let results = target.divide_by_ratio(&samples, &ratios);
+
+            assert!(check_groups(&results, &[0, 0, 0], &samples));
+        }
+    }
+
+    fn with_samples_gt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let results = target.divide_by_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_eq_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let results = target.divide_by_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_large_group_sizes();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let result = test_panic(|| {
+            target.divide_by_ratio(&samples, &ratios);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_empty_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_group_sizes_with_some_empty();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let results = target.divide_by_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_nan_ratio_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let ratios = create_group_ratios_with(f64::NAN);
+
+        let result = test_panic(|| {
+            target.divide_by_ratio(&samples, &ratios);
+        });
+
+        assert_eq!(result.message(), GroupingError::NanRatio.to_string());
+    }
+
+    fn with_infinite_ratio_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let ratios = create_group_ratios_with(f64::INFINITY);
+
+        let result = test_panic(|| {
+            target.divide_by_ratio(&samples, &ratios);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_negative_ratio_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let ratios = create_group_ratios_with(-0.3);
+
+        let result = test_panic(|| {
+            target.divide_by_ratio(&samples, &ratios);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_stable() {
+        let mut target = create_target().with_stable(true);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let results = target.divide_by_ratio(&samples, &ratios);
+
+        assert!(results.iter().all(|x| is_group_stable(x, &samples)));
+    }
+
+    fn with_rounding_floor() {
+        let mut target = create_target().with_rounding(SizeRounding::Floor);
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = vec![1.0 / 4.0; 3];
+
+        let results = target.divide_by_ratio(&samples, &ratios);
+
+        let expected_sizes = vec![2, 2, 2];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_rounding_tail() {
+        let mut target = create_target().with_rounding(SizeRounding::Tail);
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = vec![1.0 / 4.0; 4];
+
+        let results = target.divide_by_ratio(&samples, &ratios);
+
+        let expected_sizes = vec![3, 3, 3, 1];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_rounding_each() {
+        let mut target = create_target().with_rounding(SizeRounding::Each);
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = vec![1.0 / 3.0; 3];
+
+        let results = target.divide_by_ratio(&samples, &ratios);
+
+        let expected_sizes = vec![3, 4, 3];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_rounding_largest_remainder() {
+        let mut target = create_target().with_rounding(SizeRounding::LargestRemainder);
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = vec![1.0 / 3.0; 3];
+
+        let results = target.divide_by_ratio(&samples, &ratios);
+
+        let expected_sizes = vec![4, 3, 3];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+}
+
+#[test]
+fn divide_by_ratio_reported() {
+    with_samples_gt_group_totals();
+    with_empty_samples();
+    with_nan_ratio_group();
+
+    fn with_samples_gt_group_totals() {
+        let mut target = create_target();
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = [0.25, 0.25, 0.25];
+
+        let (results, report) = target.divide_by_ratio_reported(&samples, &ratios);
+
+        let expected_sizes = vec![2, 2, 2];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+        assert_eq!(report.ratios(), &ratios);
+        assert_eq!(report.sizes(), &expected_sizes);
+        assert_eq!(report.dropped(), 4);
+        assert_eq!(report.realized_ratios(), &[0.2, 0.2, 0.2]);
+    }
+
+    fn with_empty_samples() {
+        let mut target = create_target();
+        let samples = Vec::<i32>::new();
+        let ratios = [0.5, 0.5];
+
+        let (results, report) = target.divide_by_ratio_reported(&samples, &ratios);
+
+        assert!(check_groups(&results, &[0, 0], &samples));
+        assert_eq!(report.ratios(), &ratios);
+        assert_eq!(report.sizes(), &[0, 0]);
+        assert_eq!(report.dropped(), 0);
+        assert_eq!(report.realized_ratios(), &[0.0, 0.0]);
+    }
+
+    fn with_nan_ratio_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let ratios = create_group_ratios_with(f64::NAN);
+
+        let result = test_panic(|| {
+            target.divide_by_ratio_reported(&samples, &ratios);
+        });
+
+        assert_eq!(result.message(), GroupingError::NanRatio.to_string());
+    }
+}
+
+#[test]
+fn divide_by_valid_ratio() {
+    with_zero_groups();
+    with_samples_gt_group_totals();
+    with_samples_eq_group_totals();
+    with_rounding_each();
+
+    fn with_zero_groups() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = Vec::<usize>::new();
+        let ratios = Ratios::new(sizes_to_ratios(&sizes, samples.len())).unwrap();
+
+        let results = target.divide_by_valid_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_gt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+        let ratios = Ratios::new(sizes_to_ratios(&sizes, samples.len())).unwrap();
+
+        let results = target.divide_by_valid_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_eq_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let ratios = Ratios::new(sizes_to_ratios(&sizes, samples.len())).unwrap();
+
+        let results = target.divide_by_valid_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_rounding_each() {
+        let mut target = create_target().with_rounding(SizeRounding::Each);
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = Ratios::new(vec![1.0 / 3.0; 3]).unwrap();
+
+        let results = target.divide_by_valid_ratio(&samples, &ratios);
+
+        let expected_sizes = vec![3, 4, 3];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+}
+
+#[test]
+fn divide_by_relative() {
+    with_weights();
+    with_same_as_ratio();
+    with_zero_weight_sum();
+    with_negative_weight();
+
+    fn with_weights() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let weights = [3.0, 3.0, 4.0];
+
+        let results = target.divide_by_relative(&samples, &weights);
+
+        assert!(check_groups(&results, &[9, 9, 12], &samples));
+    }
+
+    fn with_same_as_ratio() {
+        let mut target_x = create_target();
+        let mut target_y = create_target();
+        let samples = create_samples();
+        let weights = [1.0, 3.0];
+
+        let results_x = target_x.divide_by_relative(&samples, &weights);
+        let results_y = target_y.divide_by_ratio(&samples, &[0.25, 0.75]);
+
+        assert_eq!(results_x, results_y);
+    }
+
+    fn with_zero_weight_sum() {
+        let mut target = create_target();
+        let samples = create_samples();
+
+        let result = test_panic(|| {
+            target.divide_by_relative(&samples, &[0.0, 0.0]);
+        });
+
+        assert_eq!(result.message(), GroupingError::ZeroWeightSum.to_string());
+    }
+
+    fn with_negative_weight() {
+        let mut target = create_target();
+        let samples = create_samples();
+
+        let result = test_panic(|| {
+            target.divide_by_relative(&samples, &[1.0, -1.0]);
+        });
+
+        assert_eq!(result.message(), GroupingError::NegativeRatio.to_string());
+    }
+}
+
+#[test]
+fn divide_slice_by_size() {
+    with_zero_groups();
+    with_samples_gt_group_totals();
+    with_samples_eq_group_totals();
+    with_samples_lt_group_totals();
+    with_stable();
+    with_dup();
+    with_empty_group();
+
+    fn with_zero_groups() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = Vec::<usize>::new();
+
+        let results = target.divide_slice_by_size(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_gt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+
+        let results = target.divide_slice_by_size(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_eq_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results = target.divide_slice_by_size(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_large_group_sizes();
+
+        let result = test_panic(|| {
+            target.divide_slice_by_size(&samples, &sizes);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_stable() {
+        let mut target = create_target().with_stable(true);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results = target.divide_slice_by_size(&samples, &sizes);
+
+        assert!(results.iter().all(|x| is_group_stable(x, &samples)));
+    }
+
+    fn with_dup() {
+        let mut target_x = create_target();
+        let mut target_y = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results_x = target_x.divide_slice_by_size(&samples, &sizes);
+        let results_y = target_y.divide_slice_by_size(&samples, &sizes);
+
+        assert_eq!(results_x, results_y);
+    }
+
+    fn with_empty_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_group_sizes_with_some_empty();
+
+        let results = target.divide_slice_by_size(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+}
+
+#[test]
+fn divide_slice_by_size_cloned() {
+    with_same_groups();
+    with_selected_clones_only();
+    with_samples_lt_group_totals();
+
+    fn with_same_groups() {
+        let mut target_x = create_target();
+        let mut target_y = create_target();
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+
+        let results_x = target_x.divide_slice_by_size_cloned(&samples, &sizes);
+        let results_y = target_y.divide_slice_by_size(&samples, &sizes);
+
+        let results_y = results_y
+            .into_iter()
+            .map(|g| g.into_iter().cloned().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(results_x, results_y);
+    }
+
+    fn with_selected_clones_only() {
+        let mut target = create_target();
+        let clones = Cell::new(0);
+        let samples = (0..100)
+            .map(|x| CountedClone(x.to_string(), &clones))
+            .collect::<Vec<_>>();
+        let sizes = [3, 5];
+
+        let results = target.divide_slice_by_size_cloned(&samples, &sizes);
+
+        assert_eq!(clones.get(), 8);
+        assert_eq!(results.iter().map(Vec::len).collect::<Vec<_>>(), sizes);
+    }
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_large_group_sizes();
+
+        let result = test_panic(|| {
+            target.divide_slice_by_size_cloned(&samples, &sizes);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    struct CountedClone<'c>(String, &'c Cell<usize>);
+
+    impl Clone for CountedClone<'_> {
+        fn clone(&self) -> Self {
+            self.1.set(self.1.get() + 1);
+            Self(self.0.clone(), self.1)
+        }
+    }
+}
+
+#[test]
+fn divide_by_size_optional() {
+    with_empty_group();
+    with_same_groups();
+    with_samples_lt_group_totals();
+
+    fn with_empty_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_group_sizes_with_some_empty();
+
+        let results = target.divide_by_size_optional(&samples, &sizes);
+
+        assert_eq!(results.len(), sizes.len());
+        assert_eq!(results[0].as_ref().map(Vec::len), Some(9));
+        assert_eq!(results[1], None);
+        assert_eq!(results[2].as_ref().map(Vec::len), Some(11));
+    }
+
+    fn with_same_groups() {
+        let mut target_x = create_target();
+        let mut target_y = create_target();
+        let samples = create_samples();
+        let sizes = create_group_sizes_with_some_empty();
+
+        let results_x = target_x.divide_by_size_optional(&samples, &sizes);
+        let results_y = target_y.divide_slice_by_size(&samples, &sizes);
+
+        let results_x = results_x.into_iter().map(Option::unwrap_or_default);
+        assert_eq!(results_x.collect::<Vec<_>>(), results_y);
+    }
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_large_group_sizes();
+
+        let result = test_panic(|| {
+            target.divide_by_size_optional(&samples, &sizes);
+        });
+
+        assert!(result.is_panic());
+    }
+}
+
+#[test]
+fn divide_slice_by_size_eq() {
+    with_position();
+    with_other_method();
+    with_same();
+    with_same_stable();
+    with_same_lt_group_totals();
+
+    fn with_position() {
+        let mut target = create_target().with_treat_equal_as_same(false);
+        let samples = [7; 10];
+        let sizes = [5, 5];
+
+        let results = target.divide_slice_by_size_eq(&samples, &sizes);
+
+        assert_eq!(results[0].len(), 5);
+        assert_eq!(results[1].len(), 5);
+    }
+
+    fn with_other_method() {
+        let mut target = create_target().with_treat_equal_as_same(true);
+        let samples = [7; 10];
+        let sizes = [5, 5];
+        let ratios = [0.5, 0.5];
+
+        let result_x = test_panic(|| {
+            target.divide_slice_by_size(&samples, &sizes);
+        });
+        let result_y = test_panic(|| {
+            target.divide_by_size(&samples, &sizes);
+        });
+        let result_z = test_panic(|| {
+            target.divide_slice_by_ratio(&samples, &ratios);
+        });
+
+        assert!(result_x.is_panic());
+        assert!(result_y.is_panic());
+        assert!(result_z.is_panic());
+    }
+
+    fn with_same() {
+        let mut target = create_target().with_treat_equal_as_same(true);
+        let samples = create_dup_samples();
+        let sizes = [2, 2];
+
+        let results = target.divide_slice_by_size_eq(&samples, &sizes);
+
+        assert_eq!(results.len(), sizes.len());
+        for group in &results {
+            let mut values = group.iter().map(|&&x| x).collect::<Vec<_>>();
+            values.dedup();
+            assert_eq!(values.len(), 2);
+            for value in values {
+                let count = samples.iter().filter(|&&x| x == value).count();
+                assert_eq!(group.iter().filter(|&&&x| x == value).count(), count);
+            }
+        }
+    }
+
+    fn with_same_stable() {
+        let mut target = create_target()
+            .with_stable(true)
+            .with_treat_equal_as_same(true);
+        let samples = create_dup_samples();
+        let sizes = [5];
+
+        let results = target.divide_slice_by_size_eq(&samples, &sizes);
+
+        assert_eq!(results[0], samples.iter().collect::<Vec<_>>());
+    }
+
+    fn with_same_lt_group_totals() {
+        let mut target = create_target().with_treat_equal_as_same(true);
+        let samples = create_dup_samples();
+        let sizes = [3, 3];
+
+        let result = test_panic(|| {
+            target.divide_slice_by_size_eq(&samples, &sizes);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn create_dup_samples() -> Vec<i32> {
+        vec![1, 1, 1, 2, 2, 3, 3, 3, 4, 5]
+    }
+}
+
+#[test]
+fn divide_by_size_least_full() {
+    with_zero_groups();
+    with_samples_gt_group_totals();
+    with_samples_eq_group_totals();
+    with_samples_lt_group_totals();
+    with_stable();
+    with_empty_group();
+    with_balance();
+
+    fn with_zero_groups() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = Vec::<usize>::new();
+
+        let results = target.divide_by_size_least_full(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_gt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+
+        let results = target.divide_by_size_least_full(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_eq_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results = target.divide_by_size_least_full(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_large_group_sizes();
+
+        let result = test_panic(|| {
+            target.divide_by_size_least_full(&samples, &sizes);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_stable() {
+        let mut target = create_target().with_stable(true);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results = target.divide_by_size_least_full(&samples, &sizes);
+
+        assert!(results.iter().all(|x| is_group_stable(x, &samples)));
+    }
+
+    fn with_empty_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_group_sizes_with_some_empty();
+
+        let results = target.divide_by_size_least_full(&samples, &sizes);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_balance() {
+        let mut rng = create_rng();
+        let mut target = RandomGrouping::from_rng(&mut rng);
+        let samples = create_samples();
+        let sizes = [10, 10, 10];
+
+        let results = target.divide_by_size_least_full(&samples, &sizes);
+
+        let draws = sample(&mut create_rng(), samples.len(), samples.len());
+        let find_group = |s: &i32| results.iter().position(|g| g.contains(&s)).unwrap();
+        let mut counts = [0; 3];
+        for idx in draws {
+            counts[find_group(&samples[idx])] += 1;
+            let (min, max) = (counts.iter().min(), counts.iter().max());
+            assert!(max.unwrap() - min.unwrap() <= 1);
+        }
+    }
+}
+
+#[test]
+fn divide_by_size_cancellable() {
+    with_samples_lt_group_totals();
+    with_not_cancelled();
+    with_cancelled();
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_large_group_sizes();
+
+        let result = test_panic(|| {
+            target.divide_by_size_cancellable(&samples, &sizes, || false);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_not_cancelled() {
+        let mut target_x = create_target();
+        let mut target_y = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let results_x = target_x.divide_by_size_cancellable(&samples, &sizes, || false);
+        let results_y = target_y.divide_slice_by_size(&samples, &sizes);
+
+        assert_eq!(results_x, Some(results_y));
+    }
+
+    fn with_cancelled() {
+        let mut target = create_target();
+        let samples = (0..10000).collect::<Vec<_>>();
+        let sizes = [3000, 3000, 3000];
+        let calls = Cell::new(0);
+        let should_cancel = || {
+            calls.set(calls.get() + 1);
+            calls.get() >= 3
+        };
+
+        let results = target.divide_by_size_cancellable(&samples, &sizes, should_cancel);
+
+        assert_eq!(results, None);
+        assert_eq!(calls.get(), 3);
+    }
+}
+
+#[test]
+fn divide_by_size_weighted_strata() {
+    with_samples_eq_group_totals();
+    with_samples_lt_group_totals();
+    with_stable();
+    with_weights_len_mismatch();
+    with_negative_weight();
+    with_nan_weight();
+    with_skewed_weights();
+    with_zero_weights();
+    with_zero_weights_random();
+
+    fn with_samples_eq_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let weights = vec![1.0; samples.len()];
+
+        let results = target.divide_by_size_weighted_strata(&samples, &sizes, &weights);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_large_group_sizes();
+        let weights = vec![1.0; samples.len()];
+
+        let result = test_panic(|| {
+            target.divide_by_size_weighted_strata(&samples, &sizes, &weights);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_stable() {
+        let mut target = create_target().with_stable(true);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let weights = vec![1.0; samples.len()];
+
+        let results = target.divide_by_size_weighted_strata(&samples, &sizes, &weights);
+
+        assert!(results.iter().all(|x| is_group_stable(x, &samples)));
+    }
+
+    fn with_weights_len_mismatch() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let weights = vec![1.0; samples.len() - 1];
+
+        let result = test_panic(|| {
+            target.divide_by_size_weighted_strata(&samples, &sizes, &weights);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_negative_weight() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let mut weights = vec![1.0; samples.len()];
+        weights[0] = -1.0;
+
+        let result = test_panic(|| {
+            target.divide_by_size_weighted_strata(&samples, &sizes, &weights);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_nan_weight() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let mut weights = vec![1.0; samples.len()];
+        weights[0] = f64::NAN;
+
+        let result = test_panic(|| {
+            target.divide_by_size_weighted_strata(&samples, &sizes, &weights);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_skewed_weights() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = [10, 10, 10];
+        let weights = samples.iter().map(|&x| if x < 10 { 100.0 } else { 1.0 });
+        let weights = weights.collect::<Vec<_>>();
+
+        let results = target.divide_by_size_weighted_strata(&samples, &sizes, &weights);
+
+        let heavy_counts = results
+            .iter()
+            .map(|g| g.iter().filter(|&&&x| x < 10).count());
+        let heavy_counts = heavy_counts.collect::<Vec<_>>();
+        assert!(check_groups(&results, &sizes, &samples));
+        assert!(heavy_counts[0] > heavy_counts[1]);
+        assert!(heavy_counts[1] >= heavy_counts[2]);
+    }
+
+    fn with_zero_weights() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = [20, 10];
+        let weights = samples.iter().map(|&x| if x < 20 { 1.0 } else { 0.0 });
+        let weights = weights.collect::<Vec<_>>();
+
+        let results = target.divide_by_size_weighted_strata(&samples, &sizes, &weights);
+
+        assert!(check_groups(&results, &sizes, &samples));
+        assert!(results[0].iter().all(|&&x| x < 20));
+    }
+
+    fn with_zero_weights_random() {
+        let samples = create_samples();
+        let sizes = [20, 5];
+        let weights = samples.iter().map(|&x| if x < 20 { 1.0 } else { 0.0 });
+        let weights = weights.collect::<Vec<_>>();
+
+        let mut zero_groups = (0..5).map(|seed| {
+            let mut target = RandomGrouping::from_seed(seed);
+            let results = target.divide_by_size_weighted_strata(&samples, &sizes, &weights);
+            results[1].iter().map(|&&x| x).collect::<Vec<_>>()
+        });
+
+        let first = zero_groups.next().unwrap();
+        assert!(first.iter().all(|&x| x >= 20));
+        assert!(zero_groups.any(|x| x != first));
+    }
+}
+
+#[test]
+fn group_labels_by_size() {
+    with_zero_groups();
+    with_samples_gt_group_totals();
+    with_samples_lt_group_totals();
+    with_empty_group();
+    with_same_as_divide();
+
+    fn with_zero_groups() {
+        let mut target = create_target();
+        let len = create_samples().len();
+
+        let result = target.group_labels_by_size(len, &[]);
+
+        assert_eq!(result, vec![UNPLACED; len]);
+    }
+
+    fn with_samples_gt_group_totals() {
+        let mut target = create_target();
+        let len = create_samples().len();
+        let sizes = create_small_group_sizes();
+
+        let result = target.group_labels_by_size(len, &sizes);
+
+        let count = |label| result.iter().filter(|&&x| x == label).count();
+        let total = sizes.iter().sum::<usize>();
+        assert_eq!(result.len(), len);
+        assert!((0..sizes.len()).all(|x| count(x) == sizes[x]));
+        assert_eq!(count(UNPLACED), len - total);
+    }
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let len = create_samples().len();
+        let sizes = create_large_group_sizes();
+
+        let result = test_panic(|| {
+            target.group_labels_by_size(len, &sizes);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_empty_group() {
+        let mut target = create_target();
+        let len = create_samples().len();
+        let sizes = create_group_sizes_with_some_empty();
+
+        let result = target.group_labels_by_size(len, &sizes);
+
+        let count = |label| result.iter().filter(|&&x| x == label).count();
+        assert!((0..sizes.len()).all(|x| count(x) == sizes[x]));
+    }
+
+    fn with_same_as_divide() {
+        let mut target_x = create_target();
+        let mut target_y = create_target();
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+
+        let labels = target_x.group_labels_by_size(samples.len(), &sizes);
+        let results = target_y.divide_slice_by_size(&samples, &sizes);
+
+        for (idx, sample) in samples.iter().enumerate() {
+            let group_idx = results.iter().position(|g| g.contains(&sample));
+            assert_eq!(labels[idx], group_idx.unwrap_or(UNPLACED));
+        }
+    }
+}
+
+#[test]
+fn repeat_stats() {
+    with_samples_lt_group_totals();
+    with_zero_runs();
+    with_constant_stat();
+    with_variable_stat();
+    with_dup();
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_large_group_sizes();
+
+        let result = test_panic(|| {
+            target.repeat_stats(&samples, &sizes, 10, |_| 0.0);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_zero_runs() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let result = test_panic(|| {
+            target.repeat_stats(&samples, &sizes, 0, |_| 0.0);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_constant_stat() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let result = target.repeat_stats(&samples, &sizes, 50, |g| g[0].len() as f64);
+
+        assert_eq!(result.mean(), sizes[0] as f64);
+        assert_eq!(result.min(), sizes[0] as f64);
+        assert_eq!(result.max(), sizes[0] as f64);
+        assert_eq!(result.stddev(), 0.0);
+    }
+
+    fn with_variable_stat() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let stat = |g: &[Vec<&i32>]| g[0].iter().map(|&&x| x as f64).sum();
+
+        let result = target.repeat_stats(&samples, &sizes, 50, stat);
+
+        assert!(result.min() < result.mean() && result.mean() < result.max());
+        assert!(result.stddev() > 0.0);
+    }
+
+    fn with_dup() {
+        let mut target_x = create_target();
+        let mut target_y = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let stat = |g: &[Vec<&i32>]| g[0].iter().map(|&&x| x as f64).sum();
+
+        let result_x = target_x.repeat_stats(&samples, &sizes, 50, stat);
+        let result_y = target_y.repeat_stats(&samples, &sizes, 50, stat);
+
+        assert_eq!(result_x, result_y);
+    }
+}
+
+#[test]
+fn best_seed_by() {
+    with_best_balance();
+    with_rng_untouched();
+    with_nan_score();
+    with_all_nan_scores();
+    with_empty_seeds();
+
+    fn with_best_balance() {
+        let target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let (seed, score) = target.best_seed_by(&samples, &sizes, 0..20, balance);
+
+        let all_scores = (0..20).map(|s| {
+            let groups = target
+                .clone_config(s)
+                .divide_slice_by_size(&samples, &sizes);
+            balance(&groups)
+        });
+        let best_groups = target
+            .clone_config(seed)
+            .divide_slice_by_size(&samples, &sizes);
+        assert!(all_scores.into_iter().all(|x| x <= score));
+        assert_eq!(balance(&best_groups), score);
+    }
+
+    fn with_rng_untouched() {
+        let mut target_x = create_target();
+        let mut target_y = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        target_x.best_seed_by(&samples, &sizes, 0..5, balance);
+        let results_x = target_x.divide_slice_by_size(&samples, &sizes);
+        let results_y = target_y.divide_slice_by_size(&samples, &sizes);
+
+        assert_eq!(results_x, results_y);
+    }
+
+    fn with_nan_score() {
+        let target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let score = |groups: &[Vec<&i32>]| match *groups[0][0] % 3 {
+            0 => f64::NAN,
+            _ => balance(groups),
+        };
+        let nan_seed = (0..).find(|&s| {
+            let groups = target
+                .clone_config(s)
+                .divide_slice_by_size(&samples, &sizes);
+            score(&groups).is_nan()
+        });
+
+        let seeds = iter::once(nan_seed.unwrap()).chain(0..20);
+        let (seed, value) = target.best_seed_by(&samples, &sizes, seeds, score);
+
+        let groups = target
+            .clone_config(seed)
+            .divide_slice_by_size(&samples, &sizes);
+        assert!(!value.is_nan());
+        assert_eq!(score(&groups), value);
+    }
+
+    fn with_all_nan_scores() {
+        let target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let result = test_panic(|| {
+            target.best_seed_by(&samples, &sizes, 0..5, |_| f64::NAN);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_empty_seeds() {
+        let target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+
+        let result = test_panic(|| {
+            target.best_seed_by(&samples, &sizes, iter::empty(), balance);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn balance(groups: &[Vec<&i32>]) -> f64 {
+        let means = groups.iter().map(|g| {
+            let total = g.iter().map(|&&x| x as f64).sum::<f64>();
+            total / g.len() as f64
+        });
+        let means = means.collect::<Vec<_>>();
+        let max = means.iter().cloned().fold(f64::MIN, f64::max);
+        let min = means.iter().cloned().fold(f64::MAX, f64::min);
+        -(max - min)
+    }
+}
+
+#[test]
+fn divide_slice_by_ratio() {
+    with_zero_groups();
+    with_empty_samples();
+    with_samples_gt_group_totals();
+    with_samples_eq_group_totals();
+    with_samples_lt_group_totals();
+    with_empty_group();
+    with_nan_ratio_group();
+    with_infinite_ratio_group();
+    with_negative_ratio_group();
+    with_stable();
+    with_rounding_floor();
+    with_rounding_tail();
+    with_rounding_each();
+    with_rounding_largest_remainder();
+
+    fn with_zero_groups() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = Vec::<usize>::new();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let results = target.divide_slice_by_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_empty_samples() {
+        let roundings = [
+            SizeRounding::Floor,
+            SizeRounding::Tail,
+            SizeRounding::Each,
+            SizeRounding::LargestRemainder,
+        ];
+        for rounding in roundings {
+            let mut target = create_target().with_rounding(rounding);
+            let samples = Vec::<i32>::new();
+            let ratios = [0.3, 0.3, 0.4];
+
+            let results = target.divide_slice_by_ratio(&samples, &ratios);
+
+            assert!(check_groups(&results, &[0, 0, 0], &samples));
+        }
+    }
+
+    fn with_samples_gt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let results = target.divide_slice_by_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_eq_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let results = target.divide_slice_by_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_large_group_sizes();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let result = test_panic(|| {
+            target.divide_slice_by_ratio(&samples, &ratios);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_empty_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_group_sizes_with_some_empty();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let results = target.divide_slice_by_ratio(&samples, &ratios);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_nan_ratio_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let ratios = create_group_ratios_with(f64::NAN);
+
+        let result = test_panic(|| {
+            target.divide_slice_by_ratio(&samples, &ratios);
+        });
+
+        assert_eq!(result.message(), GroupingError::NanRatio.to_string());
+    }
+
+    fn with_infinite_ratio_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let ratios = create_group_ratios_with(f64::INFINITY);
+
+        let result = test_panic(|| {
+            target.divide_slice_by_ratio(&samples, &ratios);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_negative_ratio_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let ratios = create_group_ratios_with(-0.3);
+
+        let result = test_panic(|| {
+            target.divide_slice_by_ratio(&samples, &ratios);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_stable() {
+        let mut target = create_target().with_stable(true);
+        let samples = create_samples();
+        let sizes = create_just_group_sizes();
+        let ratios = sizes_to_ratios(&sizes, samples.len());
+
+        let results = target.divide_slice_by_ratio(&samples, &ratios);
+
+        assert!(results.iter().all(|x| is_group_stable(x, &samples)));
+    }
+
+    fn with_rounding_floor() {
+        let mut target = create_target().with_rounding(SizeRounding::Floor);
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = vec![1.0 / 4.0; 3];
+
+        let results = target.divide_slice_by_ratio(&samples, &ratios);
+
+        let expected_sizes = vec![2, 2, 2];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_rounding_tail() {
+        let mut target = create_target().with_rounding(SizeRounding::Tail);
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = vec![1.0 / 4.0; 4];
+
+        let results = target.divide_slice_by_ratio(&samples, &ratios);
+
+        let expected_sizes = vec![3, 3, 3, 1];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_rounding_each() {
+        let mut target = create_target().with_rounding(SizeRounding::Each);
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = vec![1.0 / 3.0; 3];
+
+        let results = target.divide_slice_by_ratio(&samples, &ratios);
+
+        let expected_sizes = vec![3, 4, 3];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_rounding_largest_remainder() {
+        let mut target = create_target().with_rounding(SizeRounding::LargestRemainder);
+        let samples = (0..10).collect::<Vec<_>>();
+        let ratios = vec![1.0 / 3.0; 3];
+
+        let results = target.divide_slice_by_ratio(&samples, &ratios);
+
+        let expected_sizes = vec![4, 3, 3];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+}
+
+#[test]
+fn divide_by_basis_points() {
+    with_zero_groups();
+    with_samples_eq_group_totals();
+    with_bps_total_over();
+    with_stable();
+    with_rounding_floor();
+    with_rounding_tail();
+    with_rounding_each();
+    with_rounding_largest_remainder();
+
+    fn with_zero_groups() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = Vec::<usize>::new();
+
+        let results = target.divide_by_basis_points(&samples, &[]);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_samples_eq_group_totals() {
+        let mut target = create_target();
+        let samples = (0..100).collect::<Vec<_>>();
+        let bps = [5000, 3000, 2000];
+
+        let results = target.divide_by_basis_points(&samples, &bps);
+
+        let expected_sizes = vec![50, 30, 20];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_bps_total_over() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let bps = [5000, 3000, 2001];
+
+        let result = test_panic(|| {
+            target.divide_by_basis_points(&samples, &bps);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_stable() {
+        let mut target = create_target().with_stable(true);
+        let samples = create_samples();
+        let bps = [3000, 3000, 4000];
+
+        let results = target.divide_by_basis_points(&samples, &bps);
+
+        assert!(results.iter().all(|x| is_group_stable(x, &samples)));
+    }
+
+    fn with_rounding_floor() {
+        let mut target = create_target().with_rounding(SizeRounding::Floor);
+        let samples = (0..10).collect::<Vec<_>>();
+        let bps = [2500, 2500, 2500];
+
+        let results = target.divide_by_basis_points(&samples, &bps);
+
+        let expected_sizes = vec![2, 2, 2];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_rounding_tail() {
+        let mut target = create_target().with_rounding(SizeRounding::Tail);
+        let samples = (0..10).collect::<Vec<_>>();
+        let bps = [2500, 2500, 2500, 2500];
+
+        let results = target.divide_by_basis_points(&samples, &bps);
+
+        let expected_sizes = vec![3, 3, 3, 1];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_rounding_each() {
+        let mut target = create_target().with_rounding(SizeRounding::Each);
+        let samples = (0..10).collect::<Vec<_>>();
+        let bps = [3333, 3334, 3333];
+
+        let results = target.divide_by_basis_points(&samples, &bps);
+
+        let expected_sizes = vec![3, 4, 3];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_rounding_largest_remainder() {
+        let mut target = create_target().with_rounding(SizeRounding::LargestRemainder);
+        let samples = (0..10).collect::<Vec<_>>();
+        let bps = [3333, 3333, 3333];
+
+        let results = target.divide_by_basis_points(&samples, &bps);
+
+        let expected_sizes = vec![4, 3, 3];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+}
+
+#[test]
+fn divide_by_remaining_fractions() {
+    with_zero_groups();
+    with_cascade();
+    with_whole_rest();
+    with_empty_group();
+    with_stable();
+    with_nan_fraction();
+    with_negative_fraction();
+    with_over_one_fraction();
+
+    fn with_zero_groups() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = Vec::<usize>::new();
+
+        let results = target.divide_by_remaining_fractions(&samples, &[]);
+
+        assert!(check_groups(&results, &sizes, &samples));
+    }
+
+    fn with_cascade() {
+        let mut target = create_target();
+        let samples = (0..10).collect::<Vec<_>>();
+        let fracs = [0.5, 0.5];
+
+        let results = target.divide_by_remaining_fractions(&samples, &fracs);
+
+        let expected_sizes = vec![5, 2];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_whole_rest() {
+        let mut target = create_target();
+        let samples = (0..10).collect::<Vec<_>>();
+        let fracs = [0.3, 0.5, 1.0];
+
+        let results = target.divide_by_remaining_fractions(&samples, &fracs);
+
+        let expected_sizes = vec![3, 3, 4];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_empty_group() {
+        let mut target = create_target();
+        let samples = (0..10).collect::<Vec<_>>();
+        let fracs = [0.5, 0.0, 1.0];
+
+        let results = target.divide_by_remaining_fractions(&samples, &fracs);
+
+        let expected_sizes = vec![5, 0, 5];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+    }
+
+    fn with_stable() {
+        let mut target = create_target().with_stable(true);
+        let samples = create_samples();
+        let fracs = [0.3, 0.5, 1.0];
+
+        let results = target.divide_by_remaining_fractions(&samples, &fracs);
+
+        assert!(results.iter().all(|x| is_group_stable(x, &samples)));
+    }
+
+    fn with_nan_fraction() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let fracs = [0.5, f64::NAN];
+
+        let result = test_panic(|| {
+            target.divide_by_remaining_fractions(&samples, &fracs);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_negative_fraction() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let fracs = [0.5, -0.5];
+
+        let result = test_panic(|| {
+            target.divide_by_remaining_fractions(&samples, &fracs);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_over_one_fraction() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let fracs = [0.5, 1.5];
+
+        let result = test_panic(|| {
+            target.divide_by_remaining_fractions(&samples, &fracs);
+        });
+
+        assert!(result.is_panic());
+    }
+}
+
+#[test]
+fn divide_by_cumulative_sizes() {
+    with_zero_groups();
+    with_sizes();
+    with_empty_group();
+    with_decreasing();
+    with_samples_lt_last();
+
+    fn with_zero_groups() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let cumulative = Vec::<usize>::new();
+
+        let results = target.divide_by_cumulative_sizes(&samples, &cumulative);
+
+        assert!(results.is_empty());
+    }
+
+    fn with_sizes() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let cumulative = [9, 19, 30];
+
+        let results = target.divide_by_cumulative_sizes(&samples, &cumulative);
+
+        assert!(check_groups(&results, &[9, 10, 11], &samples));
+    }
+
+    fn with_empty_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let cumulative = [9, 9, 20];
+
+        let results = target.divide_by_cumulative_sizes(&samples, &cumulative);
+
+        assert!(check_groups(&results, &[9, 0, 11], &samples));
+    }
+
+    fn with_decreasing() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let cumulative = [9, 8, 20];
+
+        let result = test_panic(|| {
+            target.divide_by_cumulative_sizes(&samples, &cumulative);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_samples_lt_last() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let cumulative = [9, samples.len() + 1];
+
+        let result = test_panic(|| {
+            target.divide_by_cumulative_sizes(&samples, &cumulative);
+        });
+
+        assert!(result.is_panic());
+    }
+}
+
+#[test]
+fn extend_partition() {
+    with_sizes();
+    with_empty_partition();
+    with_length_mismatch();
+    with_new_samples_lt_group_totals();
+
+    fn with_sizes() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let (old_samples, new_samples) = samples.split_at(20);
+        let mut partition = target.divide_slice_by_size(old_samples, &[8, 4, 8]);
+        let old_partition = partition.clone();
+        let add_sizes = [3, 0, 7];
+
+        target.extend_partition(&mut partition, new_samples, &add_sizes);
+
+        let sizes = [11, 4, 15];
+        assert!(check_groups(&partition, &sizes, &samples));
+        for (group, old_group) in partition.iter().zip(&old_partition) {
+            assert_eq!(&group[..old_group.len()], &old_group[..]);
+            assert!(group[old_group.len()..]
+                .iter()
+                .all(|&x| new_samples.contains(x)));
+        }
+    }
+
+    fn with_empty_partition() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let mut partition = Vec::new();
+
+        target.extend_partition(&mut partition, &samples, &[]);
+
+        assert!(partition.is_empty());
+    }
+
+    fn with_length_mismatch() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let mut partition = vec![Vec::new(), Vec::new()];
+
+        let result = test_panic(|| {
+            target.extend_partition(&mut partition, &samples, &[1]);
+        });
+
+        assert!(result.is_panic());
+    }
+
+    fn with_new_samples_lt_group_totals() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let mut partition = vec![Vec::new()];
+
+        let result = test_panic(|| {
+            target.extend_partition(&mut partition, &samples, &[samples.len() + 1]);
+        });
+
+        assert!(result.is_panic());
+    }
+}
+
+#[test]
+fn divide_blocks_by_size() {
+    with_zero_groups();
+    with_samples_eq_group_totals();
+    with_samples_lt_group_totals();
+    with_short_block();
+    with_zero_block_len();
+    with_stable();
+
+    fn with_zero_groups() {
         let mut target = create_target();
         let samples = create_samples();
-        let sizes = create_large_group_sizes();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
+        let sizes = Vec::<usize>::new();
 
-        let result = test_panic(|| {
-            target.divide_by_ratio(&samples, &ratios);
-        });
+        let results = target.divide_blocks_by_size(&samples, 3, &sizes);
 
-        assert!(result.is_panic());
+        assert!(check_groups(&results, &sizes, &samples));
     }
 
-    fn with_empty_group() {
+    fn with_samples_eq_group_totals() {
         let mut target = create_target();
         let samples = create_samples();
-        let sizes = create_group_sizes_with_some_empty();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
+        let block_sizes = [3, 3, 4];
 
-        let results = target.divide_by_ratio(&samples, &ratios);
+        let results = target.divide_blocks_by_size(&samples, 3, &block_sizes);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        let expected_sizes = vec![9, 9, 12];
+        assert!(check_groups(&results, &expected_sizes, &samples));
+        assert!(results.iter().all(|x| is_blocks_kept(x, 3, &samples)));
     }
 
-    fn with_nan_ratio_group() {
+    fn with_samples_lt_group_totals() {
         let mut target = create_target();
         let samples = create_samples();
-        let ratios = create_group_ratios_with(f64::NAN);
+        let block_sizes = [3, 3, 5];
 
         let result = test_panic(|| {
-            target.divide_by_ratio(&samples, &ratios);
+            target.divide_blocks_by_size(&samples, 3, &block_sizes);
         });
 
         assert!(result.is_panic());
     }
 
-    fn with_infinite_ratio_group() {
+    fn with_short_block() {
         let mut target = create_target();
         let samples = create_samples();
-        let ratios = create_group_ratios_with(f64::INFINITY);
+        let block_sizes = [4, 4];
 
-        let result = test_panic(|| {
-            target.divide_by_ratio(&samples, &ratios);
-        });
+        let results = target.divide_blocks_by_size(&samples, 4, &block_sizes);
 
-        assert!(result.is_panic());
+        let sizes = results.iter().map(|x| x.len()).collect::<Vec<_>>();
+        assert!(check_groups(&results, &sizes, &samples));
+        assert_eq!(sizes.iter().sum::<usize>(), samples.len());
+        assert!(results.iter().all(|x| is_blocks_kept(x, 4, &samples)));
     }
 
-    fn with_negative_ratio_group() {
+    fn with_zero_block_len() {
         let mut target = create_target();
         let samples = create_samples();
-        let ratios = create_group_ratios_with(-0.3);
+        let block_sizes = [1];
 
         let result = test_panic(|| {
-            target.divide_by_ratio(&samples, &ratios);
+            target.divide_blocks_by_size(&samples, 0, &block_sizes);
         });
 
         assert!(result.is_panic());
@@ -241,95 +2650,70 @@ fn divide_by_ratio() {
     fn with_stable() {
         let mut target = create_target().with_stable(true);
         let samples = create_samples();
-        let sizes = create_just_group_sizes();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
+        let block_sizes = [3, 3, 4];
 
-        let results = target.divide_by_ratio(&samples, &ratios);
+        let results = target.divide_blocks_by_size(&samples, 3, &block_sizes);
 
         assert!(results.iter().all(|x| is_group_stable(x, &samples)));
     }
 
-    fn with_rounding_floor() {
-        let mut target = create_target().with_rounding(SizeRounding::Floor);
-        let samples = (0..10).collect::<Vec<_>>();
-        let ratios = iter::repeat(1.0 / 4.0).take(3).collect::<Vec<_>>();
-
-        let results = target.divide_by_ratio(&samples, &ratios);
-
-        let expected_sizes = vec![2, 2, 2];
-        assert!(check_groups(&results, &expected_sizes, &samples));
-    }
-
-    fn with_rounding_tail() {
-        let mut target = create_target().with_rounding(SizeRounding::Tail);
-        let samples = (0..10).collect::<Vec<_>>();
-        let ratios = iter::repeat(1.0 / 4.0).take(4).collect::<Vec<_>>();
-
-        let results = target.divide_by_ratio(&samples, &ratios);
-
-        let expected_sizes = vec![3, 3, 3, 1];
-        assert!(check_groups(&results, &expected_sizes, &samples));
-    }
-
-    fn with_rounding_each() {
-        let mut target = create_target().with_rounding(SizeRounding::Each);
-        let samples = (0..10).collect::<Vec<_>>();
-        let ratios = iter::repeat(1.0 / 3.0).take(3).collect::<Vec<_>>();
-
-        let results = target.divide_by_ratio(&samples, &ratios);
-
-        let expected_sizes = vec![3, 4, 3];
-        assert!(check_groups(&results, &expected_sizes, &samples));
+    fn is_blocks_kept(group: &[&i32], block_len: i32, samples: &[i32]) -> bool {
+        let mut iter = group.iter().map(|&&x| x);
+        while let Some(head) = iter.next() {
+            let block = head / block_len;
+            let block_end = ((block + 1) * block_len).min(samples.len() as i32);
+            if head != block * block_len || !(head + 1..block_end).all(|x| iter.next() == Some(x)) {
+                return false;
+            }
+        }
+
+        true
     }
 }
 
 #[test]
-fn divide_slice_by_size() {
-    with_zero_groups();
-    with_samples_gt_group_totals();
-    with_samples_eq_group_totals();
-    with_samples_lt_group_totals();
+fn divide_into_bins() {
+    with_empty_samples();
+    with_rest();
+    with_no_rest();
+    with_zero_max();
     with_stable();
-    with_dup();
-    with_empty_group();
 
-    fn with_zero_groups() {
+    fn with_empty_samples() {
         let mut target = create_target();
-        let samples = create_samples();
-        let sizes = Vec::<usize>::new();
+        let samples = Vec::<i32>::new();
 
-        let results = target.divide_slice_by_size(&samples, &sizes);
+        let results = target.divide_into_bins(&samples, 10);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert!(results.is_empty());
     }
 
-    fn with_samples_gt_group_totals() {
+    fn with_rest() {
         let mut target = create_target();
-        let samples = create_samples();
-        let sizes = create_small_group_sizes();
+        let samples = (0..25).collect::<Vec<_>>();
 
-        let results = target.divide_slice_by_size(&samples, &sizes);
+        let results = target.divide_into_bins(&samples, 10);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        let expected_sizes = vec![10, 10, 5];
+        assert!(check_groups(&results, &expected_sizes, &samples));
     }
 
-    fn with_samples_eq_group_totals() {
+    fn with_no_rest() {
         let mut target = create_target();
         let samples = create_samples();
-        let sizes = create_just_group_sizes();
 
-        let results = target.divide_slice_by_size(&samples, &sizes);
+        let results = target.divide_into_bins(&samples, 10);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        let expected_sizes = vec![10, 10, 10];
+        assert!(check_groups(&results, &expected_sizes, &samples));
     }
 
-    fn with_samples_lt_group_totals() {
+    fn with_zero_max() {
         let mut target = create_target();
         let samples = create_samples();
-        let sizes = create_large_group_sizes();
 
         let result = test_panic(|| {
-            target.divide_slice_by_size(&samples, &sizes);
+            target.divide_into_bins(&samples, 0);
         });
 
         assert!(result.is_panic());
@@ -338,186 +2722,306 @@ fn divide_slice_by_size() {
     fn with_stable() {
         let mut target = create_target().with_stable(true);
         let samples = create_samples();
-        let sizes = create_just_group_sizes();
 
-        let results = target.divide_slice_by_size(&samples, &sizes);
+        let results = target.divide_into_bins(&samples, 7);
 
         assert!(results.iter().all(|x| is_group_stable(x, &samples)));
     }
+}
+
+#[test]
+#[cfg(feature = "rand_distr")]
+fn random_sizes_from() {
+    with_zero_total();
+    with_uniform();
+    with_dup();
+    with_non_positive();
+
+    fn with_zero_total() {
+        let mut target = create_target();
+        let dist = Uniform::new(1.0, 6.0);
+
+        let result = target.random_sizes_from(0, dist);
+
+        assert!(result.is_empty());
+    }
+
+    fn with_uniform() {
+        let mut target = create_target();
+        let dist = Uniform::new(1.0, 6.0);
+
+        let result = target.random_sizes_from(100, dist);
+
+        assert_eq!(result.iter().sum::<usize>(), 100);
+        assert!(result.iter().all(|&x| (1..=6).contains(&x)));
+    }
 
     fn with_dup() {
         let mut target_x = create_target();
         let mut target_y = create_target();
-        let samples = create_samples();
-        let sizes = create_just_group_sizes();
+        let dist = Uniform::new(1.0, 6.0);
 
-        let results_x = target_x.divide_slice_by_size(&samples, &sizes);
-        let results_y = target_y.divide_slice_by_size(&samples, &sizes);
+        let result_x = target_x.random_sizes_from(100, dist);
+        let result_y = target_y.random_sizes_from(100, dist);
 
-        assert_eq!(results_x, results_y);
+        assert_eq!(result_x, result_y);
     }
 
-    fn with_empty_group() {
+    fn with_non_positive() {
         let mut target = create_target();
-        let samples = create_samples();
-        let sizes = create_group_sizes_with_some_empty();
+        let dist = Uniform::new(-5.0, 0.0);
 
-        let results = target.divide_slice_by_size(&samples, &sizes);
+        let result = target.random_sizes_from(10, dist);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert_eq!(result, vec![1; 10]);
     }
 }
 
 #[test]
-fn divide_slice_by_ratio() {
-    with_zero_groups();
-    with_samples_gt_group_totals();
-    with_samples_eq_group_totals();
-    with_samples_lt_group_totals();
-    with_empty_group();
-    with_nan_ratio_group();
-    with_infinite_ratio_group();
-    with_negative_ratio_group();
-    with_stable();
-    with_rounding_floor();
-    with_rounding_tail();
-    with_rounding_each();
+fn ratios_new() {
+    with_valid();
+    with_empty();
+    with_nan();
+    with_infinite();
+    with_negative();
+    with_total_over();
+    with_try_from();
 
-    fn with_zero_groups() {
-        let mut target = create_target();
-        let samples = create_samples();
-        let sizes = Vec::<usize>::new();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
+    fn with_valid() {
+        let values = vec![0.3, 0.3, 0.4];
 
-        let results = target.divide_slice_by_ratio(&samples, &ratios);
+        let result = Ratios::new(values.clone());
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert_eq!(result.unwrap().as_slice(), &values);
     }
 
-    fn with_samples_gt_group_totals() {
-        let mut target = create_target();
-        let samples = create_samples();
-        let sizes = create_small_group_sizes();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
+    fn with_empty() {
+        let result = Ratios::new(vec![]);
 
-        let results = target.divide_slice_by_ratio(&samples, &ratios);
+        assert!(result.unwrap().into_vec().is_empty());
+    }
 
-        assert!(check_groups(&results, &sizes, &samples));
+    fn with_nan() {
+        let result = Ratios::new(create_group_ratios_with(f64::NAN));
+
+        assert_eq!(result, Err(GroupingError::NanRatio));
     }
 
-    fn with_samples_eq_group_totals() {
-        let mut target = create_target();
-        let samples = create_samples();
-        let sizes = create_just_group_sizes();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
+    fn with_infinite() {
+        let result = Ratios::new(create_group_ratios_with(f64::INFINITY));
 
-        let results = target.divide_slice_by_ratio(&samples, &ratios);
+        assert_eq!(result, Err(GroupingError::InfiniteRatio));
+    }
 
-        assert!(check_groups(&results, &sizes, &samples));
+    fn with_negative() {
+        let result = Ratios::new(create_group_ratios_with(-0.3));
+
+        assert_eq!(result, Err(GroupingError::NegativeRatio));
     }
 
-    fn with_samples_lt_group_totals() {
-        let mut target = create_target();
-        let samples = create_samples();
-        let sizes = create_large_group_sizes();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
+    fn with_total_over() {
+        let result = Ratios::new(create_group_ratios_with(0.5));
 
-        let result = test_panic(|| {
-            target.divide_slice_by_ratio(&samples, &ratios);
-        });
+        assert_eq!(result, Err(GroupingError::RatioTotalOver));
+    }
 
-        assert!(result.is_panic());
+    fn with_try_from() {
+        let result_ok = Ratios::try_from(vec![0.5, 0.5]);
+        let result_err = Ratios::try_from(vec![0.5, 0.6]);
+
+        assert_eq!(result_ok.unwrap().as_ref(), &[0.5, 0.5]);
+        assert_eq!(result_err, Err(GroupingError::RatioTotalOver));
     }
+}
 
-    fn with_empty_group() {
-        let mut target = create_target();
-        let samples = create_samples();
-        let sizes = create_group_sizes_with_some_empty();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
+#[test]
+fn normalize_ratios() {
+    with_weights();
+    with_empty();
+    with_zero_weight_sum();
+    with_infinite_weight_sum();
+    with_nan();
+    with_infinite();
+    with_negative();
+    with_revalidate();
+
+    fn with_weights() {
+        let result = random_grouping::normalize_ratios(&[1.0, 1.0, 2.0]);
+
+        assert_eq!(result.unwrap().as_slice(), &[0.25, 0.25, 0.5]);
+    }
 
-        let results = target.divide_slice_by_ratio(&samples, &ratios);
+    fn with_empty() {
+        let result = random_grouping::normalize_ratios(&[]);
 
-        assert!(check_groups(&results, &sizes, &samples));
+        assert!(result.unwrap().as_slice().is_empty());
     }
 
-    fn with_nan_ratio_group() {
+    fn with_zero_weight_sum() {
+        let result = random_grouping::normalize_ratios(&[0.0, 0.0]);
+
+        assert_eq!(result, Err(GroupingError::ZeroWeightSum));
+    }
+
+    fn with_infinite_weight_sum() {
+        let result = random_grouping::normalize_ratios(&[f64::MAX, f64::MAX]);
+
+        assert_eq!(result, Err(GroupingError::InfiniteWeightSum));
+    }
+
+    fn with_nan() {
+        let result = random_grouping::normalize_ratios(&[1.0, f64::NAN]);
+
+        assert_eq!(result, Err(GroupingError::NanRatio));
+    }
+
+    fn with_infinite() {
+        let result = random_grouping::normalize_ratios(&[1.0, f64::INFINITY]);
+
+        assert_eq!(result, Err(GroupingError::InfiniteRatio));
+    }
+
+    fn with_negative() {
+        let result = random_grouping::normalize_ratios(&[1.0, -1.0]);
+
+        assert_eq!(result, Err(GroupingError::NegativeRatio));
+    }
+
+    fn with_revalidate() {
+        let mut rng = create_rng();
+        for _ in 0..10000 {
+            let len = rng.gen_range(1..10);
+            let weights = (0..len).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+            let result = random_grouping::normalize_ratios(&weights).unwrap();
+
+            let total = result.as_slice().iter().sum::<f64>();
+            assert!((1.0 - total).abs() < 1e-12);
+            assert!(Ratios::new(result.into_vec()).is_ok());
+        }
+    }
+}
+
+#[test]
+fn ratios_to_sizes_into() {
+    with_same_as_divide();
+    with_out_len_mismatch();
+    with_nan_ratio_group();
+    with_ratios_total_over();
+
+    fn with_same_as_divide() {
+        let roundings = [
+            SizeRounding::Floor,
+            SizeRounding::Tail,
+            SizeRounding::Each,
+            SizeRounding::LargestRemainder,
+        ];
+        let ratios_set = [
+            vec![],
+            vec![0.3, 0.3, 0.2],
+            vec![0.25, 0.25, 0.25, 0.25],
+            vec![1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0],
+            vec![0.15, 0.0, 0.45, 0.4],
+        ];
+
+        for rounding in roundings {
+            for ratios in &ratios_set {
+                for len in [0, 7, 10, 30] {
+                    let mut target_x = create_target().with_rounding(rounding);
+                    let mut target_y = create_target().with_rounding(rounding);
+                    let samples = (0..len).collect::<Vec<_>>();
+                    let mut result = vec![usize::MAX; ratios.len()];
+
+                    target_x.ratios_to_sizes_into(ratios, len, &mut result);
+                    let (_, report) = target_y.divide_by_ratio_reported(&samples, ratios);
+
+                    assert_eq!(result, report.sizes());
+                }
+            }
+        }
+    }
+
+    fn with_out_len_mismatch() {
         let mut target = create_target();
-        let samples = create_samples();
-        let ratios = create_group_ratios_with(f64::NAN);
+        let ratios = [0.3, 0.3, 0.2];
+        let mut out = [0; 2];
 
         let result = test_panic(|| {
-            target.divide_slice_by_ratio(&samples, &ratios);
+            target.ratios_to_sizes_into(&ratios, 10, &mut out);
         });
 
         assert!(result.is_panic());
     }
 
-    fn with_infinite_ratio_group() {
+    fn with_nan_ratio_group() {
         let mut target = create_target();
-        let samples = create_samples();
-        let ratios = create_group_ratios_with(f64::INFINITY);
+        let ratios = create_group_ratios_with(f64::NAN);
+        let mut out = [0; 3];
 
         let result = test_panic(|| {
-            target.divide_slice_by_ratio(&samples, &ratios);
+            target.ratios_to_sizes_into(&ratios, 10, &mut out);
         });
 
-        assert!(result.is_panic());
+        assert_eq!(result.message(), GroupingError::NanRatio.to_string());
     }
 
-    fn with_negative_ratio_group() {
+    fn with_ratios_total_over() {
         let mut target = create_target();
-        let samples = create_samples();
-        let ratios = create_group_ratios_with(-0.3);
+        let ratios = create_group_ratios_with(0.5);
+        let mut out = [0; 3];
 
         let result = test_panic(|| {
-            target.divide_slice_by_ratio(&samples, &ratios);
+            target.ratios_to_sizes_into(&ratios, 10, &mut out);
         });
 
         assert!(result.is_panic());
     }
+}
 
-    fn with_stable() {
-        let mut target = create_target().with_stable(true);
-        let samples = create_samples();
-        let sizes = create_just_group_sizes();
-        let ratios = sizes_to_ratios(&sizes, samples.len());
-
-        let results = target.divide_slice_by_ratio(&samples, &ratios);
-
-        assert!(results.iter().all(|x| is_group_stable(x, &samples)));
-    }
+#[test]
+fn complements() {
+    with_zero_groups();
+    with_groups();
+    with_empty_group();
 
-    fn with_rounding_floor() {
-        let mut target = create_target().with_rounding(SizeRounding::Floor);
-        let samples = (0..10).collect::<Vec<_>>();
-        let ratios = iter::repeat(1.0 / 4.0).take(3).collect::<Vec<_>>();
+    fn with_zero_groups() {
+        let groups = Vec::<Vec<&i32>>::new();
 
-        let results = target.divide_slice_by_ratio(&samples, &ratios);
+        let results = random_grouping::complements(&groups);
 
-        let expected_sizes = vec![2, 2, 2];
-        assert!(check_groups(&results, &expected_sizes, &samples));
+        assert!(results.is_empty());
     }
 
-    fn with_rounding_tail() {
-        let mut target = create_target().with_rounding(SizeRounding::Tail);
-        let samples = (0..10).collect::<Vec<_>>();
-        let ratios = iter::repeat(1.0 / 4.0).take(4).collect::<Vec<_>>();
-
-        let results = target.divide_slice_by_ratio(&samples, &ratios);
-
-        let expected_sizes = vec![3, 3, 3, 1];
-        assert!(check_groups(&results, &expected_sizes, &samples));
+    fn with_groups() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_small_group_sizes();
+        let groups = target.divide_slice_by_size(&samples, &sizes);
+
+        let results = random_grouping::complements(&groups);
+
+        let total = sizes.iter().sum::<usize>();
+        assert_eq!(results.len(), groups.len());
+        for (group, result) in groups.iter().zip(&results) {
+            let others = groups.iter().filter(|&x| x != group).flatten();
+            assert_eq!(result.len(), total - group.len());
+            assert!(result.iter().all(|x| !group.contains(x)));
+            assert!(others.into_iter().all(|x| result.contains(x)));
+        }
     }
 
-    fn with_rounding_each() {
-        let mut target = create_target().with_rounding(SizeRounding::Each);
-        let samples = (0..10).collect::<Vec<_>>();
-        let ratios = iter::repeat(1.0 / 3.0).take(3).collect::<Vec<_>>();
+    fn with_empty_group() {
+        let mut target = create_target();
+        let samples = create_samples();
+        let sizes = create_group_sizes_with_some_empty();
+        let groups = target.divide_slice_by_size(&samples, &sizes);
 
-        let results = target.divide_slice_by_ratio(&samples, &ratios);
+        let results = random_grouping::complements(&groups);
 
-        let expected_sizes = vec![3, 4, 3];
-        assert!(check_groups(&results, &expected_sizes, &samples));
+        let total = sizes.iter().sum::<usize>();
+        assert_eq!(results[1].len(), total);
+        assert_eq!(results[0], groups[2]);
+        assert_eq!(results[2], groups[0]);
     }
 }
 